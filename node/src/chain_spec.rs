@@ -258,5 +258,6 @@ fn testnet_genesis(
 			validators: initial_authorities.iter().map(|x| (x.0.clone(), x.6)).collect(),
 			asset_id_by_name: vec![("test-stable.testnet".to_string(), 0)],
 		},
+		atocha_module: Default::default(),
 	}
 }
@@ -0,0 +1,166 @@
+//! Runtime API definition for the atocha pallet.
+//!
+//! The node serves it as the `atocha_*` RPC methods, see `pallet-atocha-rpc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+pub use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// The most entries returned by one page of a list query.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// A puzzle with its prize pool and answer count.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PuzzleDetails<AccountId, Balance> {
+	pub puzzle_hash: Vec<u8>,
+	pub account: AccountId,
+	pub answer: Option<Vec<u8>>,
+	pub answer_signed: Vec<u8>,
+	pub answer_nonce: Vec<u8>,
+	pub puzzle_ticket: Balance,
+	pub puzzle_status: u8,
+	pub create_bn: u64,
+	pub duration_bn: u64,
+	pub reveal_bn: u64,
+	pub puzzle_version: u64,
+	pub prize_pool: Balance,
+	pub answer_count: u32,
+}
+
+impl<AccountId, Balance> PuzzleDetails<AccountId, Balance> {
+	/// Convert the balances, the RPC sends them as `NumberOrHex`.
+	pub fn try_map_balance<B, E>(
+		self,
+		f: impl Fn(Balance) -> Result<B, E>,
+	) -> Result<PuzzleDetails<AccountId, B>, E> {
+		Ok(PuzzleDetails {
+			puzzle_hash: self.puzzle_hash,
+			account: self.account,
+			answer: self.answer,
+			answer_signed: self.answer_signed,
+			answer_nonce: self.answer_nonce,
+			puzzle_ticket: f(self.puzzle_ticket)?,
+			puzzle_status: self.puzzle_status,
+			create_bn: self.create_bn,
+			duration_bn: self.duration_bn,
+			reveal_bn: self.reveal_bn,
+			puzzle_version: self.puzzle_version,
+			prize_pool: f(self.prize_pool)?,
+			answer_count: self.answer_count,
+		})
+	}
+}
+
+/// An answer of a puzzle.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AnswerDetails<AccountId, Balance> {
+	pub puzzle_hash: Vec<u8>,
+	pub answer_hash: Vec<u8>,
+	pub account: AccountId,
+	pub ticket: Balance,
+	pub answer_status: u8,
+	pub create_bn: u64,
+}
+
+impl<AccountId, Balance> AnswerDetails<AccountId, Balance> {
+	/// Convert the balances, the RPC sends them as `NumberOrHex`.
+	pub fn try_map_balance<B, E>(
+		self,
+		f: impl Fn(Balance) -> Result<B, E>,
+	) -> Result<AnswerDetails<AccountId, B>, E> {
+		Ok(AnswerDetails {
+			puzzle_hash: self.puzzle_hash,
+			answer_hash: self.answer_hash,
+			account: self.account,
+			ticket: f(self.ticket)?,
+			answer_status: self.answer_status,
+			create_bn: self.create_bn,
+		})
+	}
+}
+
+/// The inclusion proof of an answer in the answer root of its puzzle. The leaf is the
+/// `keccak_256` of the SCALE encoded `(account, answer_hash, ticket, create_bn)`, `proof`
+/// holds the siblings from the leaves up, a node without a sibling is carried up unchanged.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AnswerProof<AccountId, Balance> {
+	pub puzzle_hash: Vec<u8>,
+	pub answer_hash: Vec<u8>,
+	pub account: AccountId,
+	pub ticket: Balance,
+	pub create_bn: u64,
+	pub leaf_index: u32,
+	pub leaf_count: u32,
+	pub proof: Vec<H256>,
+	pub root: H256,
+}
+
+impl<AccountId, Balance> AnswerProof<AccountId, Balance> {
+	/// Convert the balances, the RPC sends them as `NumberOrHex`.
+	pub fn try_map_balance<B, E>(
+		self,
+		f: impl Fn(Balance) -> Result<B, E>,
+	) -> Result<AnswerProof<AccountId, B>, E> {
+		Ok(AnswerProof {
+			puzzle_hash: self.puzzle_hash,
+			answer_hash: self.answer_hash,
+			account: self.account,
+			ticket: f(self.ticket)?,
+			create_bn: self.create_bn,
+			leaf_index: self.leaf_index,
+			leaf_count: self.leaf_count,
+			proof: self.proof,
+			root: self.root,
+		})
+	}
+}
+
+/// The inclusion proof of the settlement leaf of a puzzle in the settlement root of the block
+/// it was settled in. The `pallet_mmr` leaf of block `settled_bn + 1` commits to that root, the
+/// leaf is `pallet_atocha::Pallet::settlement_leaf` and the proof is built like `AnswerProof`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SettlementProof {
+	pub puzzle_hash: Vec<u8>,
+	pub settled_bn: u64,
+	pub leaf: H256,
+	pub leaf_index: u32,
+	pub leaf_count: u32,
+	pub proof: Vec<H256>,
+	pub root: H256,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Queries of the puzzles and answers kept by the atocha pallet. The list queries
+	/// return at most `MAX_PAGE_SIZE` entries starting at `offset`.
+	pub trait AtochaApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The puzzle stored under `puzzle_hash`.
+		fn get_puzzle(puzzle_hash: Vec<u8>) -> Option<PuzzleDetails<AccountId, Balance>>;
+		/// The answers of a puzzle in the order they were submitted.
+		fn list_answers(puzzle_hash: Vec<u8>, offset: u32, limit: u32) -> Vec<AnswerDetails<AccountId, Balance>>;
+		/// The puzzles created by `creator`, oldest first.
+		fn puzzles_by_creator(creator: AccountId, offset: u32, limit: u32) -> Vec<PuzzleDetails<AccountId, Balance>>;
+		/// The puzzles in `puzzle_status`, in the storage order of the status index.
+		fn puzzles_by_status(puzzle_status: u8, offset: u32, limit: u32) -> Vec<PuzzleDetails<AccountId, Balance>>;
+		/// The inclusion proofs of the answers of `account` to a puzzle which is not pruned yet.
+		fn answer_proofs(puzzle_hash: Vec<u8>, account: AccountId) -> Vec<AnswerProof<AccountId, Balance>>;
+		/// The inclusion proof of the settlement of a puzzle, for the retention period after it
+		/// was settled.
+		fn settlement_proof(puzzle_hash: Vec<u8>) -> Option<SettlementProof>;
+	}
+}
@@ -0,0 +1,236 @@
+//! The `atocha_*` RPC methods, served from the `AtochaApi` runtime API.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay}};
+
+pub use pallet_atocha_rpc_runtime_api::AtochaApi as AtochaRuntimeApi;
+use pallet_atocha_rpc_runtime_api::{
+	AnswerDetails, AnswerProof, PuzzleDetails, SettlementProof, MAX_PAGE_SIZE,
+};
+
+/// The balances of the runtime are sent as `NumberOrHex`, a `u128` does not fit in a JSON
+/// number.
+#[rpc]
+pub trait AtochaApi<BlockHash, AccountId, Balance> {
+	/// The puzzle stored under `puzzle_hash`.
+	#[rpc(name = "atocha_getPuzzle")]
+	fn get_puzzle(
+		&self,
+		puzzle_hash: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<PuzzleDetails<AccountId, NumberOrHex>>>;
+
+	/// A page of the answers of a puzzle, in the order they were submitted.
+	#[rpc(name = "atocha_listAnswers")]
+	fn list_answers(
+		&self,
+		puzzle_hash: Bytes,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<AnswerDetails<AccountId, NumberOrHex>>>;
+
+	/// A page of the puzzles created by `creator`, oldest first.
+	#[rpc(name = "atocha_puzzlesByCreator")]
+	fn puzzles_by_creator(
+		&self,
+		creator: AccountId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PuzzleDetails<AccountId, NumberOrHex>>>;
+
+	/// A page of the puzzles in `puzzle_status`, in the storage order of the status index.
+	#[rpc(name = "atocha_puzzlesByStatus")]
+	fn puzzles_by_status(
+		&self,
+		puzzle_status: u8,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> Result<Vec<PuzzleDetails<AccountId, NumberOrHex>>>;
+
+	/// The inclusion proofs of the answers of `account` in the answer root of a puzzle.
+	#[rpc(name = "atocha_answerProofs")]
+	fn answer_proofs(
+		&self,
+		puzzle_hash: Bytes,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<AnswerProof<AccountId, NumberOrHex>>>;
+
+	/// The inclusion proof of the settlement of a puzzle in the MMR leaf commitment.
+	#[rpc(name = "atocha_settlementProof")]
+	fn settlement_proof(
+		&self,
+		puzzle_hash: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<SettlementProof>>;
+}
+
+/// A struct that implements the `AtochaApi`.
+pub struct Atocha<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Atocha<C, B> {
+	/// Create new `Atocha` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query the atocha pallet.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn try_into_rpc_balance<Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>>(
+	value: Balance,
+) -> Result<NumberOrHex> {
+	value.try_into().map_err(|_| RpcError {
+		code: ErrorCode::InvalidParams,
+		message: format!("{} doesn't fit in NumberOrHex representation", value),
+		data: None,
+	})
+}
+
+impl<C, Block, AccountId, Balance> AtochaApi<<Block as BlockT>::Hash, AccountId, Balance>
+	for Atocha<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AtochaRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	fn get_puzzle(
+		&self,
+		puzzle_hash: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PuzzleDetails<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_puzzle(&at, puzzle_hash.to_vec())
+			.map_err(runtime_error)?
+			.map(|puzzle_details| puzzle_details.try_map_balance(try_into_rpc_balance))
+			.transpose()
+	}
+
+	fn list_answers(
+		&self,
+		puzzle_hash: Bytes,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AnswerDetails<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.list_answers(
+			&at,
+			puzzle_hash.to_vec(),
+			offset.unwrap_or_default(),
+			limit.unwrap_or(MAX_PAGE_SIZE),
+		).map_err(runtime_error)?
+			.into_iter()
+			.map(|details| details.try_map_balance(try_into_rpc_balance))
+			.collect()
+	}
+
+	fn puzzles_by_creator(
+		&self,
+		creator: AccountId,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PuzzleDetails<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.puzzles_by_creator(
+			&at,
+			creator,
+			offset.unwrap_or_default(),
+			limit.unwrap_or(MAX_PAGE_SIZE),
+		).map_err(runtime_error)?
+			.into_iter()
+			.map(|details| details.try_map_balance(try_into_rpc_balance))
+			.collect()
+	}
+
+	fn puzzles_by_status(
+		&self,
+		puzzle_status: u8,
+		offset: Option<u32>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PuzzleDetails<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.puzzles_by_status(
+			&at,
+			puzzle_status,
+			offset.unwrap_or_default(),
+			limit.unwrap_or(MAX_PAGE_SIZE),
+		).map_err(runtime_error)?
+			.into_iter()
+			.map(|details| details.try_map_balance(try_into_rpc_balance))
+			.collect()
+	}
+
+	fn answer_proofs(
+		&self,
+		puzzle_hash: Bytes,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AnswerProof<AccountId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.answer_proofs(&at, puzzle_hash.to_vec(), account)
+			.map_err(runtime_error)?
+			.into_iter()
+			.map(|answer_proof| answer_proof.try_map_balance(try_into_rpc_balance))
+			.collect()
+	}
+
+	fn settlement_proof(
+		&self,
+		puzzle_hash: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SettlementProof>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.settlement_proof(&at, puzzle_hash.to_vec()).map_err(runtime_error)
+	}
+}
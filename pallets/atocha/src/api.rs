@@ -0,0 +1,173 @@
+// The queries behind the `AtochaApi` runtime API.
+
+use crate::merkle::{merkle_proof, merkle_root, MerkleHash};
+use crate::pallet::*;
+use pallet_atocha_rpc_runtime_api::{
+    AnswerDetails, AnswerProof, PuzzleDetails, SettlementProof, H256, MAX_PAGE_SIZE,
+};
+use sp_std::vec::Vec;
+
+pub type PuzzleDetailsOf<T> = PuzzleDetails<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+pub type AnswerDetailsOf<T> = AnswerDetails<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+pub type AnswerProofOf<T> = AnswerProof<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+impl<T: Config> Pallet<T> {
+    pub fn puzzle_details(puzzle_hash: &PuzzleSubjectHash) -> Option<PuzzleDetailsOf<T>> {
+        <PuzzleInfo<T>>::get(puzzle_hash)
+            .map(|puzzle_content| Self::to_puzzle_details(puzzle_hash.clone(), puzzle_content))
+    }
+
+    pub fn answer_details_page(
+        puzzle_hash: &PuzzleSubjectHash,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<AnswerDetailsOf<T>> {
+        Self::answers_in_order(puzzle_hash)
+            .into_iter()
+            .skip(offset as usize)
+            .take(page_size(limit))
+            .map(|(answer_hash, answer_content)| AnswerDetails {
+                puzzle_hash: puzzle_hash.clone(),
+                answer_hash,
+                account: answer_content.account,
+                ticket: answer_content.ticket,
+                answer_status: answer_content.answer_status,
+                create_bn: answer_content.create_bn,
+            })
+            .collect()
+    }
+
+    // A proof for every answer of `account`. A solved or closed puzzle is proved against its
+    // recorded answer root, an open puzzle against the answers it has now.
+    pub fn answer_proofs(
+        puzzle_hash: &PuzzleSubjectHash,
+        account: &T::AccountId,
+    ) -> Vec<AnswerProofOf<T>> {
+        let answer_list = Self::answers_in_order(puzzle_hash);
+        let leaves: Vec<MerkleHash> = answer_list
+            .iter()
+            .map(|(answer_hash, answer_content)| Self::answer_leaf(answer_hash, answer_content))
+            .collect();
+        let root = <PuzzleAnswerRoot<T>>::get(puzzle_hash).unwrap_or_else(|| merkle_root(&leaves));
+
+        answer_list
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (_, answer_content))| &answer_content.account == account)
+            .map(|(leaf_index, (answer_hash, answer_content))| AnswerProof {
+                puzzle_hash: puzzle_hash.clone(),
+                answer_hash,
+                account: answer_content.account,
+                ticket: answer_content.ticket,
+                create_bn: answer_content.create_bn,
+                leaf_index: leaf_index as u32,
+                leaf_count: leaves.len() as u32,
+                proof: merkle_proof(&leaves, leaf_index)
+                    .into_iter()
+                    .map(H256::from)
+                    .collect(),
+                root: H256::from(root),
+            })
+            .collect()
+    }
+
+    // The proof of the settlement of a puzzle against the `settlement_commitment` of the block
+    // it was settled in, while `SettledPuzzles` keeps that block.
+    pub fn settlement_proof(puzzle_hash: &PuzzleSubjectHash) -> Option<SettlementProof> {
+        let settled_bn = <PuzzleClosedAt<T>>::get(puzzle_hash).or_else(|| {
+            <PuzzleArchive<T>>::get(puzzle_hash).map(|archive_content| archive_content.closed_bn)
+        })?;
+        let settled_list = <SettledPuzzles<T>>::get(settled_bn);
+        let leaf_index = settled_list
+            .iter()
+            .position(|(settled_hash, _)| settled_hash == puzzle_hash)?;
+        let leaves: Vec<MerkleHash> = settled_list
+            .into_iter()
+            .map(|(_, settlement_leaf)| settlement_leaf)
+            .collect();
+
+        Some(SettlementProof {
+            puzzle_hash: puzzle_hash.clone(),
+            settled_bn,
+            leaf: H256::from(leaves[leaf_index]),
+            leaf_index: leaf_index as u32,
+            leaf_count: leaves.len() as u32,
+            proof: merkle_proof(&leaves, leaf_index)
+                .into_iter()
+                .map(H256::from)
+                .collect(),
+            root: H256::from(merkle_root(&leaves)),
+        })
+    }
+
+    pub fn puzzle_details_by_creator(
+        creator: &T::AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<PuzzleDetailsOf<T>> {
+        let puzzle_list: Vec<(PuzzleSubjectHash, PuzzleInfoDataOf<T>)> =
+            <PuzzlesByCreator<T>>::iter_prefix(creator)
+                .filter_map(|(puzzle_hash, _)| {
+                    <PuzzleInfo<T>>::get(&puzzle_hash)
+                        .map(|puzzle_content| (puzzle_hash, puzzle_content))
+                })
+                .collect();
+        Self::to_puzzle_details_page(puzzle_list, offset, limit)
+    }
+
+    // One page of `PuzzlesByStatus` in its storage order, only the entries up to the page are
+    // read.
+    pub fn puzzle_details_by_status(
+        puzzle_status: PuzzleStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<PuzzleDetailsOf<T>> {
+        <PuzzlesByStatus<T>>::iter_prefix(puzzle_status)
+            .skip(offset as usize)
+            .take(page_size(limit))
+            .filter_map(|(puzzle_hash, _)| Self::puzzle_details(&puzzle_hash))
+            .collect()
+    }
+
+    // One page of the puzzles, oldest first.
+    fn to_puzzle_details_page(
+        mut puzzle_list: Vec<(PuzzleSubjectHash, PuzzleInfoDataOf<T>)>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<PuzzleDetailsOf<T>> {
+        puzzle_list.sort_by_key(|(_, puzzle_content)| puzzle_content.create_bn);
+        puzzle_list
+            .into_iter()
+            .skip(offset as usize)
+            .take(page_size(limit))
+            .map(|(puzzle_hash, puzzle_content)| {
+                Self::to_puzzle_details(puzzle_hash, puzzle_content)
+            })
+            .collect()
+    }
+
+    fn to_puzzle_details(
+        puzzle_hash: PuzzleSubjectHash,
+        puzzle_content: PuzzleInfoDataOf<T>,
+    ) -> PuzzleDetailsOf<T> {
+        PuzzleDetails {
+            prize_pool: <PuzzlePrizePool<T>>::get(&puzzle_hash),
+            answer_count: <PuzzleAnswerCount<T>>::get(&puzzle_hash),
+            puzzle_hash,
+            account: puzzle_content.account,
+            answer: puzzle_content.answer.map(|answer| answer.into_inner()),
+            answer_signed: puzzle_content.answer_signed.into_inner(),
+            answer_nonce: puzzle_content.answer_nonce.into_inner(),
+            puzzle_ticket: puzzle_content.puzzle_ticket,
+            puzzle_status: puzzle_content.puzzle_status,
+            create_bn: puzzle_content.create_bn,
+            duration_bn: puzzle_content.duration_bn,
+            reveal_bn: puzzle_content.reveal_bn,
+            puzzle_version: puzzle_content.puzzle_version,
+        }
+    }
+}
+
+fn page_size(limit: u32) -> usize {
+    limit.min(MAX_PAGE_SIZE) as usize
+}
@@ -1,24 +1,548 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-atocha
 
 use super::*;
 
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin};
 use frame_system::RawOrigin;
-use frame_benchmarking::{benchmarks, whitelisted_caller, impl_benchmark_test_suite};
+use sp_std::vec;
+use sp_std::vec::Vec;
 #[allow(unused)]
-use crate::Pallet as Template;
+use crate::Pallet as Atocha;
+
+const SEED: u32 = 0;
+const DURATION: u64 = 100;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, T::Currency::minimum_balance() * 1_000_000u32.into());
+	who
+}
+
+fn ticket<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance() * 10u32.into()
+}
+
+fn puzzle_hash(h: u32) -> Vec<u8> {
+	vec![1; h as usize]
+}
+
+// The longest answer, it is revealed by the benchmarks which settle a puzzle.
+fn answer<T: Config>() -> Vec<u8> {
+	vec![5; T::MaxAnswerLength::get() as usize]
+}
+
+// The longest answer of slot `j`.
+fn slot_answer<T: Config>(j: u32) -> Vec<u8> {
+	let mut answer = j.encode();
+	answer.resize(T::MaxAnswerLength::get() as usize, 0);
+	answer
+}
+
+// The longest hash of slot `j` of answer `i`, it is never the hash of a slot answer.
+fn slot_hash<T: Config>(i: u32, j: u32) -> Vec<u8> {
+	let mut hash = (i, j).encode();
+	hash.resize(T::MaxHashLength::get() as usize, 0);
+	hash
+}
+
+// Create a puzzle and give it `a` answers from different accounts, the first one is correct.
+fn create_puzzle_with_answers<T: Config>(creator: T::AccountId, puzzle_hash: Vec<u8>, a: u32)
+where
+	u64: From<<T as frame_system::Config>::BlockNumber>,
+{
+	Atocha::<T>::create_puzzle(
+		RawOrigin::Signed(creator).into(),
+		puzzle_hash.clone(),
+		vec![2; T::MaxSignatureLength::get() as usize],
+		vec![3; T::MaxAnswerLength::get() as usize],
+		ticket::<T>(),
+		DURATION,
+		None,
+	).expect("the puzzle is created");
+	for i in 0 .. a {
+		let answerer = funded_account::<T>("answerer", i);
+		let answer_hash = if i == 0 { Atocha::<T>::make_answer_hash(&answer::<T>()) } else { i.encode() };
+		Atocha::<T>::answer_puzzle(
+			RawOrigin::Signed(answerer).into(),
+			puzzle_hash.clone(),
+			answer_hash,
+			ticket::<T>(),
+		).expect("the answer is created");
+	}
+}
+
+// Create a puzzle with `s` slots and give it `a` answers from different accounts. Every answer
+// gets the first slot right when there is more than one slot, so every answer is paid.
+fn create_slot_puzzle_with_answers<T: Config>(creator: T::AccountId, puzzle_hash: Vec<u8>, s: u32, a: u32)
+where
+	u64: From<<T as frame_system::Config>::BlockNumber>,
+{
+	Atocha::<T>::create_slot_puzzle(
+		RawOrigin::Signed(creator).into(),
+		puzzle_hash.clone(),
+		vec![vec![2; T::MaxSignatureLength::get() as usize]; s as usize],
+		vec![3; T::MaxAnswerLength::get() as usize],
+		ticket::<T>(),
+		DURATION,
+	).expect("the puzzle is created");
+	for i in 0 .. a {
+		let answerer = funded_account::<T>("answerer", i);
+		let slot_hashes: Vec<Vec<u8>> = (0 .. s).map(|j| {
+			if j == 0 && (s > 1 || i == 0) {
+				Atocha::<T>::make_answer_hash(&slot_answer::<T>(0))
+			} else {
+				slot_hash::<T>(i, j)
+			}
+		}).collect();
+		Atocha::<T>::answer_puzzle_slots(
+			RawOrigin::Signed(answerer).into(),
+			puzzle_hash.clone(),
+			slot_hashes,
+			ticket::<T>(),
+		).expect("the answer is created");
+	}
+}
+
+// Commit the answer of `a` answerers to a puzzle, each with its own salt. Return the answerers
+// and their salts.
+fn commit_answers<T: Config>(puzzle_hash: Vec<u8>, a: u32) -> Vec<(T::AccountId, Vec<u8>)>
+where
+	u64: From<<T as frame_system::Config>::BlockNumber>,
+{
+	(0 .. a).map(|i| {
+		let answerer = funded_account::<T>("answerer", i);
+		let mut salt = i.encode();
+		salt.resize(T::MaxAnswerLength::get() as usize, 0);
+		let commitment = Atocha::<T>::make_answer_commitment(&answer::<T>(), &salt, &answerer);
+		Atocha::<T>::commit_answer(
+			RawOrigin::Signed(answerer.clone()).into(),
+			puzzle_hash.clone(),
+			commitment,
+			ticket::<T>(),
+		).expect("the answer is committed");
+		(answerer, salt)
+	}).collect()
+}
+
+// Add a hint for sale to a puzzle, it unlocks half way to the deadline.
+fn add_hint_for_sale<T: Config>(creator: T::AccountId, puzzle_hash: Vec<u8>, hint_index: u32)
+where
+	u64: From<<T as frame_system::Config>::BlockNumber>,
+{
+	Atocha::<T>::add_hint(
+		RawOrigin::Signed(creator).into(),
+		puzzle_hash,
+		Atocha::<T>::make_hint_commitment(&hint_index.encode()),
+		Some(DURATION / 2),
+		Some(ticket::<T>()),
+	).expect("the hint is added");
+}
+
+// Add the most hints for sale to a puzzle and buy them `p` times, each time from another account.
+fn add_hints_with_purchases<T: Config>(creator: T::AccountId, puzzle_hash: Vec<u8>, p: u32)
+where
+	u64: From<<T as frame_system::Config>::BlockNumber>,
+{
+	for hint_index in 0 .. T::MaxHintsPerPuzzle::get() {
+		add_hint_for_sale::<T>(creator.clone(), puzzle_hash.clone(), hint_index);
+	}
+	for i in 0 .. p {
+		let buyer = funded_account::<T>("buyer", i);
+		Atocha::<T>::buy_hint(
+			RawOrigin::Signed(buyer).into(),
+			puzzle_hash.clone(),
+			i % T::MaxHintsPerPuzzle::get(),
+		).expect("the hint is bought");
+	}
+}
+
+// Fill the blocks from `after` blocks on, so a deadline or hint unlock due then is put off
+// to the last block it can wait for.
+fn fill_schedule<T: Config>(after: u64)
+where
+	u64: From<<T as frame_system::Config>::BlockNumber>,
+{
+	let first_bn = u64::from(frame_system::Pallet::<T>::block_number()) + after;
+	for scheduled_bn in first_bn .. first_bn + MAX_SCHEDULE_DELAY - 1 {
+		for _ in 0 .. T::MaxExpiriesPerBlock::get() {
+			PuzzleDeadlines::<T>::mutate(scheduled_bn, |puzzle_list| {
+				puzzle_list.try_push(vec![0]).expect("the block has room")
+			});
+			HintUnlocks::<T>::mutate(scheduled_bn, |unlock_list| {
+				unlock_list.try_push((vec![0], 0)).expect("the block has room")
+			});
+		}
+	}
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	where_clause { where u64: From<<T as frame_system::Config>::BlockNumber> }
+
+	create_puzzle {
+		let h in 1 .. T::MaxHashLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		fill_schedule::<T>(DURATION);
+	}: _(
+		RawOrigin::Signed(caller),
+		puzzle_hash(h),
+		vec![2; T::MaxSignatureLength::get() as usize],
+		vec![3; T::MaxAnswerLength::get() as usize],
+		ticket::<T>(),
+		DURATION,
+		None
+	)
+	verify {
+		assert!(PuzzleInfo::<T>::contains_key(puzzle_hash(h)));
+	}
+
+	answer_puzzle {
+		let h in 1 .. T::MaxHashLength::get();
+		let a in 0 .. T::MaxAnswersPerPuzzle::get() - 1;
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator, puzzle_hash(T::MaxHashLength::get()), a);
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		// the existing answers are the answer hash or the 4 byte encoded index, so this hash is
+		// never taken.
+		let answer_hash = vec![0xff; h as usize];
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()), answer_hash, ticket::<T>())
+	verify {
+		assert_eq!(PuzzleAnswerCount::<T>::get(puzzle_hash(T::MaxHashLength::get())), a + 1);
+	}
+
+	create_slot_puzzle {
+		let s in 1 .. T::MaxAnswerSlots::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		fill_schedule::<T>(DURATION);
+	}: _(
+		RawOrigin::Signed(caller),
+		puzzle_hash(T::MaxHashLength::get()),
+		vec![vec![2; T::MaxSignatureLength::get() as usize]; s as usize],
+		vec![3; T::MaxAnswerLength::get() as usize],
+		ticket::<T>(),
+		DURATION
+	)
+	verify {
+		assert!(PuzzleSlotsSigned::<T>::contains_key(puzzle_hash(T::MaxHashLength::get())));
+	}
+
+	revise_puzzle {
+		let h in 1 .. T::MaxHashLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		create_puzzle_with_answers::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		fill_schedule::<T>(DURATION);
+		let revised_hash = vec![4; h as usize];
+	}: _(
+		RawOrigin::Signed(caller),
+		puzzle_hash(T::MaxHashLength::get()),
+		revised_hash.clone(),
+		vec![2; T::MaxSignatureLength::get() as usize],
+		vec![3; T::MaxAnswerLength::get() as usize],
+		ticket::<T>(),
+		DURATION
+	)
+	verify {
+		assert_eq!(PuzzleRevisedTo::<T>::get(puzzle_hash(T::MaxHashLength::get())), Some(revised_hash));
+	}
+
+	answer_puzzle_slots {
+		let s in 1 .. T::MaxAnswerSlots::get();
+		let a in 0 .. T::MaxAnswersPerPuzzle::get() - 1;
+		let creator = funded_account::<T>("creator", 0);
+		create_slot_puzzle_with_answers::<T>(creator, puzzle_hash(T::MaxHashLength::get()), s, a);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		let slot_hashes: Vec<Vec<u8>> = (0 .. s).map(|j| slot_hash::<T>(u32::MAX, j)).collect();
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()), slot_hashes, ticket::<T>())
+	verify {
+		assert_eq!(PuzzleAnswerCount::<T>::get(puzzle_hash(T::MaxHashLength::get())), a + 1);
+	}
+
+	commit_answer {
+		let h in 1 .. T::MaxHashLength::get();
+		let a in 0 .. T::MaxAnswersPerPuzzle::get() - 1;
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator, puzzle_hash(T::MaxHashLength::get()), a);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		let commitment = vec![0xfe; h as usize];
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()), commitment, ticket::<T>())
+	verify {
+		assert_eq!(PuzzleAnswerCount::<T>::get(puzzle_hash(T::MaxHashLength::get())), a + 1);
+	}
+
+	// The creator signature check of `reveal_puzzle` and `reveal_puzzle_slots`. A signature of
+	// the runtime `Signature` can not be made here, so a signature which does not verify is
+	// checked, it costs the same.
+	check_answer_signed {
+		let creator = funded_account::<T>("creator", 0);
+		let answer_nonce = vec![3; T::MaxAnswerLength::get() as usize];
+		let answer_signed = vec![1; T::MaxSignatureLength::get() as usize];
+	}: {
+		Atocha::<T>::check_answer_signed(&creator, &answer::<T>(), &answer_nonce, &answer_signed);
+	}
+
+	// `reveal_puzzle` after the signature check, the first of `a` answers wins and the `p` hint
+	// purchases are refunded.
+	reveal_answers {
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), a);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+	}: {
+		Atocha::<T>::do_reveal_puzzle(&puzzle_hash(T::MaxHashLength::get()), puzzle_content, &answer::<T>())?;
+	}
+	verify {
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+	}
+
+	// `reveal_puzzle_slots` after the signature checks of `s` slots, the `a` answers win and the
+	// `p` hint purchases are refunded.
+	reveal_slot_answers {
+		let s in 1 .. T::MaxAnswerSlots::get();
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		create_slot_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), s, a);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		let answers: Vec<Vec<u8>> = (0 .. s).map(|j| slot_answer::<T>(j)).collect();
+	}: {
+		Atocha::<T>::do_reveal_slot_puzzle(&puzzle_hash(T::MaxHashLength::get()), puzzle_content, answers)?;
+	}
+	verify {
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+	}
+
+	reveal_answer {
+		let creator = funded_account::<T>("creator", 0);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		create_puzzle_with_answers::<T>(creator, puzzle_hash(T::MaxHashLength::get()), 0);
+		let (caller, salt) = commit_answers::<T>(puzzle_hash(T::MaxHashLength::get()), 1).remove(0);
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		Atocha::<T>::do_reveal_puzzle(&puzzle_hash(T::MaxHashLength::get()), puzzle_content, &answer::<T>())?;
+		let commitment = Atocha::<T>::make_answer_commitment(&answer::<T>(), &salt, &caller);
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()), answer::<T>(), salt)
+	verify {
+		let answer_content = PuzzleAnswers::<T>::get(puzzle_hash(T::MaxHashLength::get()), commitment).unwrap();
+		assert_eq!(answer_content.answer_status, PUZZLE_ANSWER_STATUS_IS_CORRECT);
+	}
+
+	// every committed answer is revealed and wins, the `p` hint purchases are refunded.
+	settle_puzzle {
+		let a in 1 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let committed = commit_answers::<T>(puzzle_hash(T::MaxHashLength::get()), a);
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		Atocha::<T>::do_reveal_puzzle(&puzzle_hash(T::MaxHashLength::get()), puzzle_content, &answer::<T>())?;
+		for (answerer, salt) in committed {
+			Atocha::<T>::reveal_answer(
+				RawOrigin::Signed(answerer).into(),
+				puzzle_hash(T::MaxHashLength::get()),
+				answer::<T>(),
+				salt,
+			)?;
+		}
+		let reveal_bn: T::BlockNumber = 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(reveal_bn + T::AnswerRevealWindow::get());
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()))
+	verify {
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+	}
+
+	refund_puzzle {
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), a);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let refund_bn: T::BlockNumber = (1 + DURATION as u32).into();
+		frame_system::Pallet::<T>::set_block_number(refund_bn + T::RevealGracePeriod::get());
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()))
+	verify {
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_CLOSED);
+	}
+
+	revoke_puzzle {
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		fill_schedule::<T>(DURATION);
+		create_puzzle_with_answers::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), a);
+		add_hints_with_purchases::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()))
+	verify {
+		assert!(!PuzzleInfo::<T>::contains_key(puzzle_hash(T::MaxHashLength::get())));
+	}
+
+	add_hint {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		create_puzzle_with_answers::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		fill_schedule::<T>(DURATION / 2);
+	}: _(
+		RawOrigin::Signed(caller),
+		puzzle_hash(T::MaxHashLength::get()),
+		Atocha::<T>::make_hint_commitment(&0u32.encode()),
+		Some(DURATION / 2),
+		Some(ticket::<T>())
+	)
+	verify {
+		assert_eq!(PuzzleHintCount::<T>::get(puzzle_hash(T::MaxHashLength::get())), 1);
+	}
+
+	buy_hint {
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		add_hint_for_sale::<T>(creator, puzzle_hash(T::MaxHashLength::get()), 0);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), puzzle_hash(T::MaxHashLength::get()), 0)
+	verify {
+		assert!(HintPurchases::<T>::contains_key(puzzle_hash(T::MaxHashLength::get()), (0, caller)));
+	}
+
+	reveal_hint {
+		let h in 1 .. T::MaxHintLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		create_puzzle_with_answers::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		// the hint unlocks at once.
+		let hint = vec![7; h as usize];
+		Atocha::<T>::add_hint(
+			RawOrigin::Signed(caller.clone()).into(),
+			puzzle_hash(T::MaxHashLength::get()),
+			Atocha::<T>::make_hint_commitment(&hint),
+			Some(0),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()), 0, hint)
+	verify {
+		let hint_content = PuzzleHints::<T>::get(puzzle_hash(T::MaxHashLength::get()), 0).unwrap();
+		assert!(hint_content.content.is_some());
+	}
+
+	deliver_hint {
+		let h in 1 .. T::MaxHintLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() * 1_000_000u32.into());
+		create_puzzle_with_answers::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		add_hint_for_sale::<T>(caller.clone(), puzzle_hash(T::MaxHashLength::get()), 0);
+		let buyer = funded_account::<T>("buyer", 0);
+		Atocha::<T>::buy_hint(RawOrigin::Signed(buyer.clone()).into(), puzzle_hash(T::MaxHashLength::get()), 0)?;
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()), 0, buyer.clone(), vec![8; h as usize])
+	verify {
+		assert!(HintDeliveries::<T>::contains_key(puzzle_hash(T::MaxHashLength::get()), (0, buyer)));
+	}
+
+	// a refunded puzzle with `a` answers, the most hints and `p` hint purchases is pruned.
+	reap_puzzle {
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), a);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let refund_bn: T::BlockNumber = (1 + DURATION as u32).into();
+		frame_system::Pallet::<T>::set_block_number(refund_bn + T::RevealGracePeriod::get());
+		Atocha::<T>::refund_puzzle(RawOrigin::Signed(creator).into(), puzzle_hash(T::MaxHashLength::get()))?;
+		frame_system::Pallet::<T>::set_block_number(
+			refund_bn + T::RevealGracePeriod::get() + T::RetentionPeriod::get()
+		);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), puzzle_hash(T::MaxHashLength::get()))
+	verify {
+		assert!(PuzzleArchive::<T>::contains_key(puzzle_hash(T::MaxHashLength::get())));
+	}
+
+	claim_payout {
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator, puzzle_hash(T::MaxHashLength::get()), 0);
+		let caller: T::AccountId = whitelisted_caller();
+		UnpaidPayouts::<T>::insert(&caller, (puzzle_hash(T::MaxHashLength::get()), None), ticket::<T>());
+		T::Currency::make_free_balance_be(
+			&Atocha::<T>::account_id(),
+			T::Currency::minimum_balance() * 1_000_000u32.into(),
+		);
+	}: _(RawOrigin::Signed(caller.clone()), puzzle_hash(T::MaxHashLength::get()), None)
+	verify {
+		assert!(!UnpaidPayouts::<T>::contains_key(caller, (puzzle_hash(T::MaxHashLength::get()), None)));
+	}
+
+	// the new season is checked against the last one.
+	schedule_season {
+		Seasons::<T>::insert(1, SeasonData { start_bn: 10, end_bn: 20, bonus: ticket::<T>() });
+		NextSeasonId::<T>::put(2);
+		let origin = T::SeasonOrigin::successful_origin();
+	}: _<T::Origin>(origin, 20, 40, ticket::<T>())
+	verify {
+		assert!(Seasons::<T>::contains_key(2));
+	}
+
+	force_close_puzzle {
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), a);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, puzzle_hash(T::MaxHashLength::get()))
+	verify {
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_CLOSED);
+	}
+
+	force_reveal_puzzle {
+		let a in 0 .. T::MaxAnswersPerPuzzle::get();
+		let p in 0 .. Atocha::<T>::max_hint_purchases();
+		let creator = funded_account::<T>("creator", 0);
+		create_puzzle_with_answers::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), a);
+		add_hints_with_purchases::<T>(creator.clone(), puzzle_hash(T::MaxHashLength::get()), p);
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, puzzle_hash(T::MaxHashLength::get()), answer::<T>())
+	verify {
+		let puzzle_content = PuzzleInfo::<T>::get(puzzle_hash(T::MaxHashLength::get())).unwrap();
+		assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+	}
+
+	set_blacklisted {
+		let who: T::AccountId = account("blacklisted", 0, SEED);
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, who.clone(), true)
+	verify {
+		assert!(Blacklist::<T>::contains_key(who));
+	}
+
+	set_paused {
+		let origin = T::AdminOrigin::successful_origin();
+	}: _<T::Origin>(origin, true)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(Paused::<T>::get());
 	}
 }
 
 impl_benchmark_test_suite!(
-	Template,
+	Atocha,
 	crate::mock::new_test_ext(),
 	crate::mock::Test,
 );
@@ -2,12 +2,20 @@
 
 pub use pallet::*;
 
+pub mod api;
+pub mod merkle;
+mod migrations;
+pub mod weights;
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[frame_support::pallet]
 pub mod pallet {
 
@@ -16,7 +24,6 @@ pub mod pallet {
     pub type PuzzleSubjectHash = Vec<u8>;
     pub type PuzzleAnswerHash = Vec<u8>;
     pub type PuzzleAnswerOption = Option<PuzzleAnswerHash>;
-    pub type PuzzleTicket = u64;
 
     pub type PuzzleAnswerSigned = Vec<u8>;
     pub type PuzzleAnswerNonce = Vec<u8>;
@@ -26,20 +33,300 @@ pub mod pallet {
     pub type PuzzleVersion = u64;
 
     pub type PuzzleAnswerStatus = u8;
+    pub type SeasonId = u32;
+    pub type HintIndex = u32;
+    pub type HintCommitment = [u8; 32];
 
-    // 1=solving, 2=up to time, 3=solve
+    // 1=solving, 2=up to time, 3=solve, 4=closed, 5=answer revealing
     pub const PUZZLE_STATUS_IS_SOLVING: PuzzleStatus = 1;
     pub const PUZZLE_STATUS_IS_UP_TO_TIME: PuzzleStatus = 2;
     pub const PUZZLE_STATUS_IS_SOLVED: PuzzleStatus = 3;
+    pub const PUZZLE_STATUS_IS_CLOSED: PuzzleStatus = 4;
+    pub const PUZZLE_STATUS_IS_ANSWER_REVEALING: PuzzleStatus = 5;
+
+    // 0=pending, 1=correct, 2=wrong, 3=expired, 4=committed
+    pub const PUZZLE_ANSWER_STATUS_IS_PENDING: PuzzleAnswerStatus = 0;
+    pub const PUZZLE_ANSWER_STATUS_IS_CORRECT: PuzzleAnswerStatus = 1;
+    pub const PUZZLE_ANSWER_STATUS_IS_WRONG: PuzzleAnswerStatus = 2;
+    pub const PUZZLE_ANSWER_STATUS_IS_EXPIRED: PuzzleAnswerStatus = 3;
+    pub const PUZZLE_ANSWER_STATUS_IS_COMMITTED: PuzzleAnswerStatus = 4;
+
+    // The most blocks a puzzle expiry or a hint unlock is put off when its block is full.
+    pub const MAX_SCHEDULE_DELAY: u64 = 10;
+
+    // Every puzzle is created at this version, only `revise_puzzle` raises it.
+    pub const FIRST_PUZZLE_VERSION: PuzzleVersion = 1;
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    // The bounded forms of the byte fields kept in storage.
+    pub type BoundedAnswerOf<T> = BoundedVec<u8, <T as Config>::MaxAnswerLength>;
+    pub type BoundedAnswerSignedOf<T> = BoundedVec<u8, <T as Config>::MaxSignatureLength>;
+    pub type BoundedAnswerHashOf<T> = BoundedVec<u8, <T as Config>::MaxHashLength>;
+    pub type BoundedHintOf<T> = BoundedVec<u8, <T as Config>::MaxHintLength>;
+    // The per-slot creator signatures, answers and answer hashes of a slot puzzle.
+    pub type BoundedSlotsSignedOf<T> =
+        BoundedVec<BoundedAnswerSignedOf<T>, <T as Config>::MaxAnswerSlots>;
+    pub type BoundedSlotAnswersOf<T> =
+        BoundedVec<BoundedAnswerOf<T>, <T as Config>::MaxAnswerSlots>;
+    pub type BoundedSlotHashesOf<T> =
+        BoundedVec<BoundedAnswerHashOf<T>, <T as Config>::MaxAnswerSlots>;
+
+    // A puzzle, block numbers are stored as u64 like `CreateBn`.
+    // `Answer` holds the revealed answer and the nonce, `Signed` the creator signature.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct PuzzleInfoData<AccountId, Balance, BlockNumber, Answer, Signed> {
+        pub account: AccountId,
+        pub answer: Option<Answer>,
+        pub answer_signed: Signed,
+        pub answer_nonce: Answer,
+        pub puzzle_ticket: Balance,
+        pub puzzle_status: PuzzleStatus,
+        pub create_bn: BlockNumber,
+        pub duration_bn: BlockNumber,
+        pub reveal_bn: BlockNumber,
+        pub puzzle_version: PuzzleVersion,
+    }
+
+    // An answer submitted to a puzzle, `answer_index` is its place in the order the answers of
+    // the puzzle were submitted.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct AnswerData<AccountId, Balance, BlockNumber, AnswerHash> {
+        pub account: AccountId,
+        pub answer_hash: AnswerHash,
+        pub ticket: Balance,
+        pub answer_status: PuzzleAnswerStatus,
+        pub create_bn: BlockNumber,
+        pub answer_index: u32,
+    }
+
+    // A hint of a puzzle. Only the `commitment`, the `sha2_256` of the hint, is stored when it
+    // is added. From `unlock_bn` the creator publishes the hint in `content` with `reveal_hint`,
+    // before that it can be bought for `price` and the creator delivers it to the buyer with
+    // `deliver_hint`.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct HintData<Balance, Hint> {
+        pub commitment: HintCommitment,
+        pub content: Option<Hint>,
+        pub unlock_bn: Option<u64>,
+        pub price: Option<Balance>,
+        pub unlocked: bool,
+        pub purchase_count: u32,
+    }
+
+    pub type PuzzleInfoDataOf<T> = PuzzleInfoData<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        CreateBn,
+        BoundedAnswerOf<T>,
+        BoundedAnswerSignedOf<T>,
+    >;
+    pub type AnswerDataOf<T> = AnswerData<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        CreateBn,
+        BoundedAnswerHashOf<T>,
+    >;
+    pub type HintDataOf<T> = HintData<BalanceOf<T>, BoundedHintOf<T>>;
+
+    // The summary of a pruned puzzle. `answer_root` is the `merkle` root of its answers in
+    // the order they were submitted, see `answer_leaf`.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct PuzzleArchiveData<AccountId, Balance> {
+        pub account: AccountId,
+        pub puzzle_status: PuzzleStatus,
+        pub create_bn: u64,
+        pub closed_bn: u64,
+        pub winners: Vec<AccountId>,
+        pub answer_count: u32,
+        pub answer_root: crate::merkle::MerkleHash,
+        pub payouts: Balance,
+    }
+
+    pub type PuzzleArchiveDataOf<T> =
+        PuzzleArchiveData<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+    // The storage layout of the pallet, used by `on_runtime_upgrade`.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    pub enum Releases {
+        // `PuzzleInfo` and `PuzzleDirectAnswer` stored as tuples.
+        V1,
+        // `PuzzleInfo` and `PuzzleDirectAnswer` stored as `PuzzleInfoData` and `AnswerData`.
+        V2,
+        // answers moved from `PuzzleDirectAnswer` to `PuzzleAnswers` and `AnswersByAccount`.
+        V3,
+        // puzzles indexed by creator in `PuzzlesByCreator`.
+        V4,
+        // solved and closed puzzles scheduled for pruning in `PrunableAt`.
+        V5,
+        // the pot account holds the existential deposit.
+        V6,
+        // puzzles indexed by status in `PuzzlesByStatus`.
+        V7,
+        // answers numbered in submission order by `AnswerData::answer_index`.
+        V8,
+        // unpaid payouts keyed by their asset in `UnpaidPayouts`.
+        V9,
+    }
+
+    impl Default for Releases {
+        fn default() -> Self {
+            Releases::V1
+        }
+    }
+
+    // How the prize pool is split among the correct answers, in the order they were submitted.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    pub enum PrizeDistribution {
+        // every winner gets the same amount.
+        EqualSplit,
+        // the n winners are weighted n, n-1, ..., 1 by answer order.
+        FirstComeWeighted,
+        // the first winner gets the whole pool.
+        WinnerTakesAll,
+    }
+
+    // The points of a settled puzzle, `solve_blocks` is the answer block number minus the
+    // puzzle `create_bn`.
+    pub trait PointsFormula {
+        fn solver_points(solve_blocks: u64) -> u64;
+        fn creator_points(winner_count: u32) -> u64;
+    }
+
+    // The reputation of an account over all settled puzzles.
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+    pub struct PlayerScore {
+        pub points: u64,
+        pub solved_count: u32,
+        pub total_solve_blocks: u64,
+        pub created_solved_count: u32,
+    }
+
+    // A season, points are counted for the season from `start_bn` until `end_bn`. `bonus` is
+    // paid from the season treasury to the final leaderboard.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct SeasonData<Balance> {
+        pub start_bn: u64,
+        pub end_bn: u64,
+        pub bonus: Balance,
+    }
 
     // 引入需要的包
-    use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+    use crate::weights::WeightInfo;
+    use frame_support::{
+        dispatch::DispatchResultWithPostInfo,
+        pallet_prelude::*,
+        traits::{fungibles, BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+        transactional, PalletId,
+    };
     // 比较粗暴的引用 frame_system 所有宏函数，和系统类型信息
     use frame_system::pallet_prelude::*;
-    use sp_std::vec::Vec;
+    use sp_io::hashing::sha2_256;
+    use sp_runtime::{
+        helpers_128bit::multiply_by_rational,
+        traits::{
+            AccountIdConversion, IdentifyAccount, SaturatedConversion, Saturating, Verify, Zero,
+        },
+        Perbill,
+    };
+    use sp_std::{convert::TryInto, vec::Vec};
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        // The currency which the puzzle and answer tickets are paid with.
+        type Currency: ReservableCurrency<Self::AccountId>;
+        // The id of the assets a puzzle can be priced in instead of `Currency`.
+        type AssetId: Parameter + Member + Copy + Default;
+        // The assets which the tickets of a puzzle created with an asset id are paid with.
+        type Assets: fungibles::Transfer<
+            Self::AccountId,
+            AssetId = Self::AssetId,
+            Balance = BalanceOf<Self>,
+        >;
+        // The pallet id, used to derive the pot account which holds the tickets.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+        // The part of the prize pool paid to the creator when a puzzle is solved.
+        #[pallet::constant]
+        type CreatorFee: Get<Perbill>;
+        // How the rest of the prize pool is split among the winners.
+        #[pallet::constant]
+        type PrizeDistribution: Get<PrizeDistribution>;
+        // The most puzzles moved to "up to time", and the most hints unlocked, in one block. A
+        // puzzle or hint due in a full block waits for the next block with room.
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+        // The blocks after the deadline the creator has to reveal, after that the puzzle can be refunded.
+        #[pallet::constant]
+        type RevealGracePeriod: Get<Self::BlockNumber>;
+        // The part of the creator ticket given to the answerers when a puzzle is refunded.
+        #[pallet::constant]
+        type UnrevealedSlash: Get<Perbill>;
+        // The blocks after creation the creator can still revoke a puzzle which has answers.
+        #[pallet::constant]
+        type RevokeCutoff: Get<Self::BlockNumber>;
+        // The blocks after the puzzle is revealed the committed answers can be revealed.
+        #[pallet::constant]
+        type AnswerRevealWindow: Get<Self::BlockNumber>;
+        // The longest puzzle hash or answer hash.
+        #[pallet::constant]
+        type MaxHashLength: Get<u32>;
+        // The longest revealed answer or answer nonce.
+        #[pallet::constant]
+        type MaxAnswerLength: Get<u32>;
+        // The longest creator signature of the answer.
+        #[pallet::constant]
+        type MaxSignatureLength: Get<u32>;
+        // The most answers a puzzle accepts.
+        #[pallet::constant]
+        type MaxAnswersPerPuzzle: Get<u32>;
+        // The deposit reserved from the creator for every puzzle.
+        #[pallet::constant]
+        type PuzzleDepositBase: Get<BalanceOf<Self>>;
+        // The deposit reserved from the creator for every byte of a puzzle or hint.
+        #[pallet::constant]
+        type DepositPerByte: Get<BalanceOf<Self>>;
+        // The deposit reserved from the answerer for every answer.
+        #[pallet::constant]
+        type AnswerDeposit: Get<BalanceOf<Self>>;
+        // The blocks a closed puzzle is kept before it can be pruned to `PuzzleArchive`.
+        #[pallet::constant]
+        type RetentionPeriod: Get<Self::BlockNumber>;
+        // The part of the creator deposit kept until a closed puzzle is pruned, it is paid to
+        // the caller of `reap_puzzle`.
+        #[pallet::constant]
+        type ReapBounty: Get<BalanceOf<Self>>;
+        // The most answer slots of a slot puzzle.
+        #[pallet::constant]
+        type MaxAnswerSlots: Get<u32>;
+        // The most hints a creator can attach to a puzzle.
+        #[pallet::constant]
+        type MaxHintsPerPuzzle: Get<u32>;
+        // The most accounts which can buy one hint.
+        #[pallet::constant]
+        type MaxHintPurchases: Get<u32>;
+        // The longest hint.
+        #[pallet::constant]
+        type MaxHintLength: Get<u32>;
+        // The points given to the winners and the creator of a settled puzzle.
+        type PointsFormula: PointsFormula;
+        // The number of accounts kept in `Leaderboard`.
+        #[pallet::constant]
+        type LeaderboardSize: Get<u32>;
+        // The origin which closes, reveals and blacklists puzzles or pauses the pallet.
+        type AdminOrigin: EnsureOrigin<Self::Origin>;
+        // The origin which schedules seasons.
+        type SeasonOrigin: EnsureOrigin<Self::Origin>;
+        // The id of the treasury account which pays the season bonus.
+        #[pallet::constant]
+        type SeasonTreasuryId: Get<PalletId>;
+        // The public key of the puzzle creator, it must be able to identify the creator account.
+        type Public: IdentifyAccount<AccountId = Self::AccountId>;
+        // The signature used by the creator to sign `answer + nonce` when creating a puzzle.
+        type Signature: Verify<Signer = Self::Public> + Decode;
+        // Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -57,43 +344,350 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn puzzle_info)]
-    pub type PuzzleInfo<T: Config> = StorageMap<
+    pub type PuzzleInfo<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, PuzzleInfoDataOf<T>>;
+
+    // Replaced by `PuzzleAnswers`, only read by the `V3` migration.
+    #[pallet::storage]
+    pub(super) type PuzzleDirectAnswer<T: Config> = StorageMap<
         _,
         Blake2_128Concat,
         PuzzleSubjectHash,
-        (
-            T::AccountId,
-            PuzzleAnswerOption,
-            PuzzleAnswerSigned,
-            PuzzleAnswerNonce,
-            PuzzleTicket,
-            PuzzleStatus,
-            CreateBn,
-            DurationBn,
-            RevealBn,
-            PuzzleVersion,
-        ),
+        BoundedVec<AnswerDataOf<T>, T::MaxAnswersPerPuzzle>,
     >;
 
+    // The answers of a puzzle keyed by answer hash.
     #[pallet::storage]
-    #[pallet::getter(fn puzzle_direct_answer)]
-    pub type PuzzleDirectAnswer<T: Config> = StorageMap<
+    #[pallet::getter(fn puzzle_answers)]
+    pub type PuzzleAnswers<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         PuzzleSubjectHash,
-        // puzzle_hash, answer_hash, ticket (Balance type), relation_type (1=Creater, 2=Answer), status (1=solving, 2=up to time, 3=solve), create_bn, expired_bn
-        Vec<(
-            T::AccountId,
-            PuzzleAnswerHash,
-            PuzzleTicket,
-            PuzzleAnswerStatus,
-            CreateBn,
-        )>,
+        Blake2_128Concat,
+        PuzzleAnswerHash,
+        AnswerDataOf<T>,
+    >;
+
+    // The number of answers of a puzzle, limited by `MaxAnswersPerPuzzle`.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_answer_count)]
+    pub type PuzzleAnswerCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, u32, ValueQuery>;
+
+    // The answers submitted by an account, keyed by (puzzle_hash, answer_hash).
+    #[pallet::storage]
+    #[pallet::getter(fn answers_by_account)]
+    pub type AnswersByAccount<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        (PuzzleSubjectHash, PuzzleAnswerHash),
+        (),
+    >;
+
+    // The puzzles created by an account.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzles_by_creator)]
+    pub type PuzzlesByCreator<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        (),
+    >;
+
+    // The puzzles in a status, kept with `PuzzleInfoData::puzzle_status` by `set_puzzle_status`.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzles_by_status)]
+    pub type PuzzlesByStatus<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, PuzzleStatus, Blake2_128Concat, PuzzleSubjectHash, ()>;
+
+    // The creator ticket plus all answer tickets of a puzzle, held by the pot account.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_prize_pool)]
+    pub type PuzzlePrizePool<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, BalanceOf<T>, ValueQuery>;
+
+    // The payouts which could not be transferred when a puzzle was settled, keyed by the
+    // puzzle and the asset they are paid in. A puzzle hash created again in another asset keeps
+    // its payouts apart. The account takes them with `claim_payout`.
+    #[pallet::storage]
+    #[pallet::getter(fn unpaid_payouts)]
+    pub type UnpaidPayouts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        (PuzzleSubjectHash, Option<T::AssetId>),
+        BalanceOf<T>,
+    >;
+
+    // The total amount paid out of the prize pool of a puzzle.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_payouts)]
+    pub type PuzzlePayouts<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, BalanceOf<T>, ValueQuery>;
+
+    // The puzzles moved to "up to time" at the given block number, from their deadline on.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_deadlines)]
+    pub type PuzzleDeadlines<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        DurationBn,
+        BoundedVec<PuzzleSubjectHash, T::MaxExpiriesPerBlock>,
+        ValueQuery,
+    >;
+
+    // The storage deposit of a puzzle and its hints, reserved from the creator until the
+    // puzzle is closed.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_deposits)]
+    pub type PuzzleDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, BalanceOf<T>, ValueQuery>;
+
+    // The storage deposit of an answer, reserved from the answerer until the puzzle is closed.
+    #[pallet::storage]
+    #[pallet::getter(fn answer_deposits)]
+    pub type AnswerDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        Blake2_128Concat,
+        PuzzleAnswerHash,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    // The block number a puzzle was solved or closed at.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_closed_at)]
+    pub type PuzzleClosedAt<T: Config> = StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, u64>;
+
+    // The closed puzzles which are pruned in `on_idle` from the given block number.
+    #[pallet::storage]
+    #[pallet::getter(fn prunable_at)]
+    pub type PrunableAt<T: Config> =
+        StorageMap<_, Twox64Concat, u64, Vec<PuzzleSubjectHash>, ValueQuery>;
+
+    // The first block number of `PrunableAt` which is not pruned yet, 0 if nothing was closed.
+    #[pallet::storage]
+    #[pallet::getter(fn prune_cursor)]
+    pub type PruneCursor<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    // The `merkle` root of the answers of a solved or closed puzzle, see `answer_leaf`.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_answer_root)]
+    pub type PuzzleAnswerRoot<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, crate::merkle::MerkleHash>;
+
+    // The `settlement_leaf` of every puzzle solved or closed in a block, the runtime commits to
+    // them in the `pallet_mmr` leaf of that block, see `settlement_commitment`. Kept for the
+    // retention period, so `settlement_proof` can prove a settlement against that leaf.
+    #[pallet::storage]
+    #[pallet::getter(fn settled_puzzles)]
+    pub type SettledPuzzles<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        Vec<(PuzzleSubjectHash, crate::merkle::MerkleHash)>,
+        ValueQuery,
+    >;
+
+    // The summaries of the pruned puzzles.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_archive)]
+    pub type PuzzleArchive<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, PuzzleArchiveDataOf<T>>;
+
+    // The asset the tickets and rewards of a puzzle are paid in, the puzzles without an entry
+    // use `Currency`.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_asset)]
+    pub type PuzzleAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, T::AssetId>;
+
+    // The creator signature of every slot of a slot puzzle, the puzzles without an entry
+    // have a single answer.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_slots_signed)]
+    pub type PuzzleSlotsSigned<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, BoundedSlotsSignedOf<T>>;
+
+    // The revealed answer of every slot of a slot puzzle.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_slot_answers)]
+    pub type PuzzleSlotAnswers<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, BoundedSlotAnswersOf<T>>;
+
+    // The per-slot answer hashes of an answer to a slot puzzle.
+    #[pallet::storage]
+    #[pallet::getter(fn answer_slot_hashes)]
+    pub type AnswerSlotHashes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        Blake2_128Concat,
+        PuzzleAnswerHash,
+        BoundedSlotHashesOf<T>,
+    >;
+
+    // The number of slots an answer to a slot puzzle got right, set when the puzzle is revealed.
+    #[pallet::storage]
+    #[pallet::getter(fn answer_correct_slots)]
+    pub type AnswerCorrectSlots<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        Blake2_128Concat,
+        PuzzleAnswerHash,
+        u32,
+        ValueQuery,
+    >;
+
+    // The hints of a puzzle by index, in the order they were added.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_hints)]
+    pub type PuzzleHints<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        Twox64Concat,
+        HintIndex,
+        HintDataOf<T>,
+    >;
+
+    // The number of hints of a puzzle.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_hint_count)]
+    pub type PuzzleHintCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, u32, ValueQuery>;
+
+    // The hints unlocked at the given block number, from their unlock block number on.
+    #[pallet::storage]
+    #[pallet::getter(fn hint_unlocks)]
+    pub type HintUnlocks<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        BoundedVec<(PuzzleSubjectHash, HintIndex), T::MaxExpiriesPerBlock>,
+        ValueQuery,
+    >;
+
+    // The accounts which bought a hint of a puzzle.
+    #[pallet::storage]
+    #[pallet::getter(fn hint_purchases)]
+    pub type HintPurchases<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        Blake2_128Concat,
+        (HintIndex, T::AccountId),
+        (),
+    >;
+
+    // The hints delivered to their buyers, sealed to the key of the buyer by the creator.
+    #[pallet::storage]
+    #[pallet::getter(fn hint_deliveries)]
+    pub type HintDeliveries<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        PuzzleSubjectHash,
+        Blake2_128Concat,
+        (HintIndex, T::AccountId),
+        BoundedHintOf<T>,
+    >;
+
+    // The next version of a puzzle, set by `revise_puzzle`.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_revised_to)]
+    pub type PuzzleRevisedTo<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, PuzzleSubjectHash>;
+
+    // The previous version of a puzzle, set by `revise_puzzle`.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_revised_from)]
+    pub type PuzzleRevisedFrom<T: Config> =
+        StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, PuzzleSubjectHash>;
+
+    // The points and solve statistics of an account.
+    #[pallet::storage]
+    #[pallet::getter(fn player_scores)]
+    pub type PlayerScores<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, PlayerScore, ValueQuery>;
+
+    // The `LeaderboardSize` accounts with the most points, highest first. Accounts with the
+    // same points keep the order they reached them.
+    #[pallet::storage]
+    #[pallet::getter(fn leaderboard)]
+    pub type Leaderboard<T: Config> = StorageValue<_, Vec<(T::AccountId, u64)>, ValueQuery>;
+
+    // The scheduled seasons, ids start from 1 and the seasons do not overlap.
+    #[pallet::storage]
+    #[pallet::getter(fn seasons)]
+    pub type Seasons<T: Config> = StorageMap<_, Twox64Concat, SeasonId, SeasonData<BalanceOf<T>>>;
+
+    // The id given to the next scheduled season.
+    #[pallet::storage]
+    #[pallet::getter(fn next_season_id)]
+    pub type NextSeasonId<T: Config> = StorageValue<_, SeasonId, ValueQuery>;
+
+    // The season which is counting points, if any.
+    #[pallet::storage]
+    #[pallet::getter(fn active_season)]
+    pub type ActiveSeason<T: Config> = StorageValue<_, SeasonId>;
+
+    // The last season which started, the next one starts from `Seasons` at this id + 1.
+    #[pallet::storage]
+    #[pallet::getter(fn last_started_season)]
+    pub type LastStartedSeason<T: Config> = StorageValue<_, SeasonId, ValueQuery>;
+
+    // The season a puzzle was created in.
+    #[pallet::storage]
+    #[pallet::getter(fn puzzle_season)]
+    pub type PuzzleSeason<T: Config> = StorageMap<_, Blake2_128Concat, PuzzleSubjectHash, SeasonId>;
+
+    // The points and solve statistics of an account in a season.
+    #[pallet::storage]
+    #[pallet::getter(fn season_scores)]
+    pub type SeasonScores<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SeasonId,
+        Blake2_128Concat,
+        T::AccountId,
+        PlayerScore,
+        ValueQuery,
     >;
 
+    // The leaderboard of a season like `Leaderboard`, kept as the final leaderboard once the
+    // season ends.
+    #[pallet::storage]
+    #[pallet::getter(fn season_leaderboard)]
+    pub type SeasonLeaderboard<T: Config> =
+        StorageMap<_, Twox64Concat, SeasonId, Vec<(T::AccountId, u64)>, ValueQuery>;
+
+    // The accounts which can not create puzzles.
+    #[pallet::storage]
+    #[pallet::getter(fn blacklist)]
+    pub type Blacklist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    // Every signed call fails while the pallet is paused.
+    #[pallet::storage]
+    #[pallet::getter(fn paused)]
+    pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    // The storage layout version. A chain started from `atochaSpec.json` is on `V1`, a chain
+    // started with the current genesis on the latest layout.
+    #[pallet::storage]
+    #[pallet::getter(fn storage_version)]
+    pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
     #[pallet::event]
     // Make a metadata, used by WebUI
-    #[pallet::metadata(T::AccountId = "AccountId")]
+    #[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
     // Make a help methods, used by the caller
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -101,6 +695,75 @@ pub mod pallet {
         PuzzleCreated(T::AccountId, PuzzleSubjectHash, CreateBn, DurationBn),
         AnswerCreated(T::AccountId, PuzzleAnswerHash, PuzzleSubjectHash, CreateBn),
         PuzzleRevoked(T::AccountId, Vec<u8>),
+        // creator id, puzzle_hash, revealed answer, reveal block number,
+        PuzzleRevealed(T::AccountId, PuzzleSubjectHash, Vec<u8>, RevealBn),
+        // winner id, puzzle_hash, answer_hash,
+        AnswerSolved(T::AccountId, PuzzleSubjectHash, PuzzleAnswerHash),
+        // winner id, puzzle_hash, reward amount,
+        RewardPaid(T::AccountId, PuzzleSubjectHash, BalanceOf<T>),
+        // creator id, puzzle_hash, fee amount,
+        CreatorFeePaid(T::AccountId, PuzzleSubjectHash, BalanceOf<T>),
+        // puzzle_hash, duration block number,
+        PuzzleExpired(PuzzleSubjectHash, DurationBn),
+        // account id, puzzle_hash, refund amount,
+        TicketRefunded(T::AccountId, PuzzleSubjectHash, BalanceOf<T>),
+        // puzzle_hash, refund block number,
+        PuzzleRefunded(PuzzleSubjectHash, CreateBn),
+        // answerer id, commitment, puzzle_hash, create block number,
+        AnswerCommitted(T::AccountId, PuzzleAnswerHash, PuzzleSubjectHash, CreateBn),
+        // answerer id, puzzle_hash, commitment, revealed answer,
+        AnswerRevealed(T::AccountId, PuzzleSubjectHash, PuzzleAnswerHash, Vec<u8>),
+        // puzzle_hash, settle block number,
+        PuzzleSettled(PuzzleSubjectHash, RevealBn),
+        // creator id, previous puzzle_hash, new puzzle_hash, new puzzle version,
+        PuzzleRevised(
+            T::AccountId,
+            PuzzleSubjectHash,
+            PuzzleSubjectHash,
+            PuzzleVersion,
+        ),
+        // account id, puzzle_hash, points,
+        PointsAwarded(T::AccountId, PuzzleSubjectHash, u64),
+        // season id, start block number, end block number, bonus,
+        SeasonScheduled(SeasonId, u64, u64, BalanceOf<T>),
+        // season id, start block number,
+        SeasonStarted(SeasonId, u64),
+        // season id, end block number,
+        SeasonEnded(SeasonId, u64),
+        // season id, account id, bonus amount,
+        SeasonBonusPaid(SeasonId, T::AccountId, BalanceOf<T>),
+        // puzzle_hash,
+        PuzzleForceClosed(PuzzleSubjectHash),
+        // puzzle_hash, revealed answer,
+        PuzzleForceRevealed(PuzzleSubjectHash, Vec<u8>),
+        // account id, blacklisted,
+        AccountBlacklisted(T::AccountId, bool),
+        // paused,
+        PalletPaused(bool),
+        // creator id, puzzle_hash, hint index,
+        HintAdded(T::AccountId, PuzzleSubjectHash, HintIndex),
+        // puzzle_hash, hint index, unlock block number,
+        HintUnlocked(PuzzleSubjectHash, HintIndex, u64),
+        // buyer id, puzzle_hash, hint index, price,
+        HintPurchased(T::AccountId, PuzzleSubjectHash, HintIndex, BalanceOf<T>),
+        // puzzle_hash, hint index,
+        HintRevealed(PuzzleSubjectHash, HintIndex),
+        // buyer id, puzzle_hash, hint index,
+        HintDelivered(T::AccountId, PuzzleSubjectHash, HintIndex),
+        // creator id, puzzle_hash, revealed slot answers, reveal block number,
+        PuzzleSlotsRevealed(T::AccountId, PuzzleSubjectHash, Vec<Vec<u8>>, RevealBn),
+        // winner id, puzzle_hash, answer_hash, correct slots,
+        AnswerSlotsSolved(T::AccountId, PuzzleSubjectHash, PuzzleAnswerHash, u32),
+        // puzzle_hash, answer merkle root,
+        AnswerRootRecorded(PuzzleSubjectHash, crate::merkle::MerkleHash),
+        // puzzle_hash, close block number,
+        PuzzleArchived(PuzzleSubjectHash, u64),
+        // reaper id, puzzle_hash, bounty amount,
+        PuzzleReaped(T::AccountId, PuzzleSubjectHash, BalanceOf<T>),
+        // recipient id, puzzle_hash, amount,
+        PayoutDeferred(T::AccountId, PuzzleSubjectHash, BalanceOf<T>),
+        // recipient id, puzzle_hash, amount,
+        PayoutClaimed(T::AccountId, PuzzleSubjectHash, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -113,135 +776,2482 @@ pub mod pallet {
         AnswerAlreadyExist,
         PuzzleNotExist,
         NotPuzzleOwner,
+        PuzzleAlreadyRevealed,
+        AnswerNonceMismatch,
+        InvalidAnswerSignature,
+        PuzzleNotSolving,
+        PuzzleUpToTime,
+        RevealPeriodExpired,
+        RevealPeriodNotExpired,
+        RevokeCutoffPassed,
+        HashTooLong,
+        AnswerTooLong,
+        NonceTooLong,
+        SignatureTooLong,
+        TooManyAnswers,
+        CommitmentNotExist,
+        AnswerAlreadyRevealed,
+        AnswerRevealNotOpen,
+        AnswerRevealPeriodExpired,
+        AnswerRevealPeriodNotExpired,
+        PuzzleAlreadyRevised,
+        InvalidSeasonPeriod,
+        SeasonOverlaps,
+        CreatorBlacklisted,
+        PalletPaused,
+        HintTooLong,
+        TooManyHints,
+        TooManyHintPurchases,
+        HintNeverAvailable,
+        HintNotExist,
+        HintNotForSale,
+        HintAlreadyAvailable,
+        HintNotUnlocked,
+        HintAlreadyRevealed,
+        HintCommitmentMismatch,
+        HintNotPurchased,
+        HintAlreadyDelivered,
+        InvalidSlotCount,
+        SlotCountMismatch,
+        NotSlotPuzzle,
+        SlotAnswersRequired,
+        PuzzleNotClosed,
+        RetentionPeriodNotOver,
+        NoUnpaidPayout,
+        InvalidDuration,
+        ScheduleFull,
+    }
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig;
+
+    #[cfg(feature = "std")]
+    impl Default for GenesisConfig {
+        fn default() -> Self {
+            Self
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            // a new chain starts on the latest layout, there is nothing to migrate.
+            <StorageVersion<T>>::put(Releases::V9);
+            // the pot is shared by all puzzles, it must never be reaped.
+            let _ = T::Currency::make_free_balance_be(
+                &<Pallet<T>>::account_id(),
+                T::Currency::minimum_balance(),
+            );
+        }
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            if <StorageVersion<T>>::get() == Releases::V1 {
+                weight = weight.saturating_add(crate::migrations::v2::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V2 {
+                weight = weight.saturating_add(crate::migrations::v3::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V3 {
+                weight = weight.saturating_add(crate::migrations::v4::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V4 {
+                weight = weight.saturating_add(crate::migrations::v5::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V5 {
+                weight = weight.saturating_add(crate::migrations::v6::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V6 {
+                weight = weight.saturating_add(crate::migrations::v7::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V7 {
+                weight = weight.saturating_add(crate::migrations::v8::migrate::<T>());
+            }
+            if <StorageVersion<T>>::get() == Releases::V8 {
+                weight = weight.saturating_add(crate::migrations::v9::migrate::<T>());
+            }
+            weight
+        }
+
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::prune_closed_puzzles(now.saturated_into(), remaining_weight)
+        }
+
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let now: DurationBn = now.saturated_into();
+
+            // both lists hold at most `MaxExpiriesPerBlock` entries.
+            let expired_list = <PuzzleDeadlines<T>>::take(now);
+            let expired_count = expired_list.len() as Weight;
+            for puzzle_hash in expired_list.into_inner() {
+                Self::expire_puzzle(puzzle_hash, now);
+            }
+
+            let unlock_list = <HintUnlocks<T>>::take(now);
+            let unlock_count = unlock_list.len() as Weight;
+            for (puzzle_hash, hint_index) in unlock_list.into_inner() {
+                Self::unlock_hint(puzzle_hash, hint_index, now);
+            }
+
+            // the mmr leaf of this block commits to the settlements of the parent block, they
+            // are kept as long as the settled puzzles.
+            let retention: u64 = T::RetentionPeriod::get().saturated_into();
+            <SettledPuzzles<T>>::remove(now.saturating_sub(retention.saturating_add(2)));
+
+            let season_weight = Self::roll_seasons(now);
+
+            T::DbWeight::get()
+                .reads_writes(
+                    4 + expired_count + unlock_count,
+                    5 + 3 * expired_count + unlock_count,
+                )
+                .saturating_add(season_weight)
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T>
     where
         u64: From<<T as frame_system::Config>::BlockNumber>,
     {
-        #[pallet::weight(1234)]
+        // Create a puzzle, with `asset_id` its tickets and rewards are paid in that asset.
+        #[pallet::weight(T::WeightInfo::create_puzzle(puzzle_hash.len() as u32))]
+        #[transactional]
         pub fn create_puzzle(
             origin: OriginFor<T>,
             puzzle_hash: PuzzleSubjectHash,
             answer_signed: PuzzleAnswerSigned,
             answer_nonce: PuzzleAnswerNonce,
-            ticket: PuzzleTicket,
+            ticket: BalanceOf<T>,
             duration: DurationBn,
-            puzzle_version: PuzzleVersion,
+            asset_id: Option<T::AssetId>,
         ) -> DispatchResultWithPostInfo {
             // check signer
             let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            Self::do_create_puzzle(
+                &who,
+                &puzzle_hash,
+                answer_signed,
+                answer_nonce,
+                ticket,
+                duration,
+                (FIRST_PUZZLE_VERSION, asset_id),
+            )?;
             //
-            let current_block_number = <frame_system::Pallet<T>>::block_number();
-            let dration_block_number = duration.checked_add(current_block_number.into()).unwrap();
+            Ok(().into())
+        }
+
+        // Create a puzzle with an answer for every slot, `slots_signed` holds the creator
+        // signature of `answer + nonce` of each slot.
+        #[pallet::weight(T::WeightInfo::create_slot_puzzle(slots_signed.len() as u32))]
+        #[transactional]
+        pub fn create_slot_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            slots_signed: Vec<PuzzleAnswerSigned>,
+            answer_nonce: PuzzleAnswerNonce,
+            ticket: BalanceOf<T>,
+            duration: DurationBn,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            ensure!(!slots_signed.is_empty(), Error::<T>::InvalidSlotCount);
+            let slots_signed = slots_signed
+                .into_iter()
+                .map(|answer_signed| answer_signed.try_into())
+                .collect::<Result<Vec<BoundedAnswerSignedOf<T>>, _>>()
+                .map_err(|_| Error::<T>::SignatureTooLong)?;
+            let slots_signed: BoundedSlotsSignedOf<T> = slots_signed
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidSlotCount)?;
+
+            // the single answer signature is not used by a slot puzzle.
+            Self::do_create_puzzle(
+                &who,
+                &puzzle_hash,
+                Vec::new(),
+                answer_nonce,
+                ticket,
+                duration,
+                (FIRST_PUZZLE_VERSION, None),
+            )?;
+            let slots_bytes = slots_signed.iter().fold(0usize, |total, answer_signed| {
+                total.saturating_add(answer_signed.len())
+            });
+            Self::reserve_puzzle_deposit(&who, &puzzle_hash, Self::bytes_deposit(slots_bytes))?;
+            <PuzzleSlotsSigned<T>>::insert(&puzzle_hash, slots_signed);
+            //
+            Ok(().into())
+        }
+
+        // Publish a new version of a puzzle, the previous version stops accepting answers
+        // but keeps its answers and prize pool.
+        #[pallet::weight(T::WeightInfo::revise_puzzle(puzzle_hash.len() as u32))]
+        #[transactional]
+        pub fn revise_puzzle(
+            origin: OriginFor<T>,
+            previous_hash: PuzzleSubjectHash,
+            puzzle_hash: PuzzleSubjectHash,
+            answer_signed: PuzzleAnswerSigned,
+            answer_nonce: PuzzleAnswerNonce,
+            ticket: BalanceOf<T>,
+            duration: DurationBn,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
 
+            // Only the latest version of a puzzle which is still open can be revised.
+            let previous_content =
+                <PuzzleInfo<T>>::get(&previous_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(previous_content.account == who, Error::<T>::NotPuzzleOwner);
             ensure!(
-                !<PuzzleInfo<T>>::contains_key(&puzzle_hash),
-                Error::<T>::PuzzleAlreadyExist
+                previous_content.answer.is_none()
+                    && previous_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
             );
-
-            type PuzzleContent<T> = (
-                <T as frame_system::Config>::AccountId,
-                PuzzleAnswerOption,
-                PuzzleAnswerSigned,
-                PuzzleAnswerNonce,
-                PuzzleTicket,
-                PuzzleStatus,
-                CreateBn,
-                DurationBn,
-                RevealBn,
-                PuzzleVersion,
+            ensure!(
+                !<PuzzleRevisedTo<T>>::contains_key(&previous_hash),
+                Error::<T>::PuzzleAlreadyRevised
             );
 
-            let puzzle_content: PuzzleContent<T> = (
-                who.clone(),
-                None,
-                answer_signed.clone(),
-                answer_nonce.clone(),
+            // a revision is paid in the asset of the previous version.
+            let puzzle_version = previous_content.puzzle_version.saturating_add(1);
+            Self::do_create_puzzle(
+                &who,
+                &puzzle_hash,
+                answer_signed,
+                answer_nonce,
                 ticket,
-                PUZZLE_STATUS_IS_SOLVING,
-                current_block_number.clone().into(),
-                dration_block_number.clone(),
-                0,
-                1,
-            );
-            <PuzzleInfo<T>>::insert(puzzle_hash.clone(), puzzle_content);
+                duration,
+                (puzzle_version, <PuzzleAsset<T>>::get(&previous_hash)),
+            )?;
+            <PuzzleRevisedTo<T>>::insert(&previous_hash, &puzzle_hash);
+            <PuzzleRevisedFrom<T>>::insert(&puzzle_hash, &previous_hash);
 
             // send event
-            Self::deposit_event(Event::PuzzleCreated(
+            Self::deposit_event(Event::PuzzleRevised(
                 who,
+                previous_hash,
                 puzzle_hash,
-                current_block_number.into(),
-                dration_block_number,
+                puzzle_version,
             ));
             //
             Ok(().into())
         }
 
-        #[pallet::weight(1234)]
+        // constant in the number of answers: the duplicate check and the insert touch one entry.
+        #[pallet::weight(T::WeightInfo::answer_puzzle(
+            answer_hash.len() as u32,
+            T::MaxAnswersPerPuzzle::get()
+        ))]
+        #[transactional]
         pub fn answer_puzzle(
             origin: OriginFor<T>,
             puzzle_hash: PuzzleSubjectHash,
             answer_hash: PuzzleAnswerHash,
-            ticket: PuzzleTicket,
+            ticket: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
             // check signer
             let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
 
-            //
-            let current_block_number = <frame_system::Pallet<T>>::block_number();
-
-            //(T::AccountId, PuzzleAnswerHash, PuzzleTicket, PuzzleAnswerStatus, CreateBn)
-            type AnswerContent<T> = (
-                <T as frame_system::Config>::AccountId,
-                PuzzleAnswerHash,
-                PuzzleTicket,
-                PuzzleAnswerStatus,
-                CreateBn,
-            );
-
-            // Puzzle need exists.
-            ensure!(
-                <PuzzleInfo<T>>::contains_key(&puzzle_hash),
-                Error::<T>::PuzzleNotExist
-            );
-
-            let mut answer_store_list: Vec<AnswerContent<T>> = Vec::new();
-            let answer_list_opt = <PuzzleDirectAnswer<T>>::get(&puzzle_hash);
-            if let Some(answer_list) = answer_list_opt {
-                // Determine whether the answer already exists.
-                let answer_exists = answer_list
-                    .iter()
-                    .any(|(_, old_answer_hash, _, _, _)| &answer_hash == old_answer_hash);
-                ensure!(!answer_exists, Error::<T>::AnswerAlreadyExist);
-                answer_store_list = answer_list;
-            }
-
-            // create new answer tuple.
-            let answer_content: AnswerContent<T> = (
-                who.clone(),
-                answer_hash.clone(),
+            let create_bn = Self::do_answer_puzzle(
+                &who,
+                &puzzle_hash,
+                &answer_hash,
                 ticket,
-                0, // 1=solving ???, 2=NONE, 3=solved ????4=closed ???
-                current_block_number.clone().into(),
-            );
-            answer_store_list.push(answer_content);
-
-            <PuzzleDirectAnswer<T>>::insert(puzzle_hash.clone(), answer_store_list);
+                PUZZLE_ANSWER_STATUS_IS_PENDING,
+                None,
+            )?;
 
             // send event
             Self::deposit_event(Event::AnswerCreated(
                 who,
                 answer_hash,
                 puzzle_hash,
-                current_block_number.into(),
+                create_bn,
             ));
             //
             Ok(().into())
         }
+
+        // Answer a slot puzzle with the answer hash of every slot, the answer is stored under
+        // `sha2_256` of the encoded slot hashes. The weight is for the most slots of the longest
+        // hashes, the bounds are checked before the slot hashes are hashed.
+        #[pallet::weight(T::WeightInfo::answer_puzzle_slots(
+            T::MaxAnswerSlots::get(),
+            T::MaxAnswersPerPuzzle::get()
+        ))]
+        #[transactional]
+        pub fn answer_puzzle_slots(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            slot_hashes: Vec<PuzzleAnswerHash>,
+            ticket: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            ensure!(
+                slot_hashes.len() <= T::MaxAnswerSlots::get() as usize,
+                Error::<T>::SlotCountMismatch
+            );
+            let bounded_slot_hashes = slot_hashes
+                .into_iter()
+                .map(|slot_hash| slot_hash.try_into())
+                .collect::<Result<Vec<BoundedAnswerHashOf<T>>, _>>()
+                .map_err(|_| Error::<T>::HashTooLong)?;
+            let bounded_slot_hashes: BoundedSlotHashesOf<T> = bounded_slot_hashes
+                .try_into()
+                .map_err(|_| Error::<T>::SlotCountMismatch)?;
+            // a bounded vec encodes like the vec it holds.
+            let answer_hash = Self::make_answer_hash(&bounded_slot_hashes.encode());
+
+            let create_bn = Self::do_answer_puzzle(
+                &who,
+                &puzzle_hash,
+                &answer_hash,
+                ticket,
+                PUZZLE_ANSWER_STATUS_IS_PENDING,
+                Some(bounded_slot_hashes),
+            )?;
+
+            // send event
+            Self::deposit_event(Event::AnswerCreated(
+                who,
+                answer_hash,
+                puzzle_hash,
+                create_bn,
+            ));
+            //
+            Ok(().into())
+        }
+
+        // Commit `sha2_256(answer + salt + account)`, the answer is revealed with `reveal_answer`
+        // after the creator reveals the puzzle, so the commitment is useless to anyone else.
+        #[pallet::weight(T::WeightInfo::commit_answer(
+            commitment.len() as u32,
+            T::MaxAnswersPerPuzzle::get()
+        ))]
+        #[transactional]
+        pub fn commit_answer(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            commitment: PuzzleAnswerHash,
+            ticket: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let create_bn = Self::do_answer_puzzle(
+                &who,
+                &puzzle_hash,
+                &commitment,
+                ticket,
+                PUZZLE_ANSWER_STATUS_IS_COMMITTED,
+                None,
+            )?;
+
+            // send event
+            Self::deposit_event(Event::AnswerCommitted(
+                who,
+                commitment,
+                puzzle_hash,
+                create_bn,
+            ));
+            //
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::reveal_answer())]
+        pub fn reveal_answer(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            answer: Vec<u8>,
+            salt: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            //
+            let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+            // Puzzle need exists, and wait for the committed answers.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.puzzle_status == PUZZLE_STATUS_IS_ANSWER_REVEALING,
+                Error::<T>::AnswerRevealNotOpen
+            );
+            let current_bn: RevealBn = current_block_number.into();
+            ensure!(
+                current_bn < Self::answer_reveal_deadline(puzzle_content.reveal_bn),
+                Error::<T>::AnswerRevealPeriodExpired
+            );
+
+            // the commitment is bound to the caller, nobody else can reveal it.
+            let commitment = Self::make_answer_commitment(&answer, &salt, &who);
+            let mut answer_content = <PuzzleAnswers<T>>::get(&puzzle_hash, &commitment)
+                .ok_or(Error::<T>::CommitmentNotExist)?;
+            ensure!(
+                answer_content.answer_status == PUZZLE_ANSWER_STATUS_IS_COMMITTED,
+                Error::<T>::AnswerAlreadyRevealed
+            );
+
+            let is_correct = puzzle_content
+                .answer
+                .map_or(false, |puzzle_answer| puzzle_answer[..] == answer[..]);
+            answer_content.answer_status = if is_correct {
+                PUZZLE_ANSWER_STATUS_IS_CORRECT
+            } else {
+                PUZZLE_ANSWER_STATUS_IS_WRONG
+            };
+            <PuzzleAnswers<T>>::insert(&puzzle_hash, &commitment, answer_content);
+
+            // send event
+            Self::deposit_event(Event::AnswerRevealed(
+                who.clone(),
+                puzzle_hash.clone(),
+                commitment.clone(),
+                answer,
+            ));
+            if is_correct {
+                Self::deposit_event(Event::AnswerSolved(who, puzzle_hash, commitment));
+            }
+            //
+            Ok(().into())
+        }
+
+        // Pay the prize pool once the answer reveal window is over, the commitments which are
+        // not revealed are wrong.
+        #[pallet::weight(T::WeightInfo::settle_puzzle(
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ))]
+        #[transactional]
+        pub fn settle_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            //
+            let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+            // Puzzle need exists, and the answer reveal window is over.
+            let mut puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.puzzle_status == PUZZLE_STATUS_IS_ANSWER_REVEALING,
+                Error::<T>::AnswerRevealNotOpen
+            );
+            let current_bn: RevealBn = current_block_number.into();
+            ensure!(
+                current_bn >= Self::answer_reveal_deadline(puzzle_content.reveal_bn),
+                Error::<T>::AnswerRevealPeriodNotExpired
+            );
+
+            let mut winners: Vec<T::AccountId> = Vec::new();
+            for (answer_hash, mut answer_content) in Self::answers_in_order(&puzzle_hash) {
+                if answer_content.answer_status == PUZZLE_ANSWER_STATUS_IS_CORRECT {
+                    winners.push(answer_content.account);
+                } else if answer_content.answer_status == PUZZLE_ANSWER_STATUS_IS_COMMITTED {
+                    answer_content.answer_status = PUZZLE_ANSWER_STATUS_IS_WRONG;
+                    <PuzzleAnswers<T>>::insert(&puzzle_hash, answer_hash, answer_content);
+                }
+            }
+
+            Self::set_puzzle_status(&puzzle_hash, &mut puzzle_content, PUZZLE_STATUS_IS_SOLVED);
+            let creator = puzzle_content.account.clone();
+            let create_bn = puzzle_content.create_bn;
+            <PuzzleInfo<T>>::insert(puzzle_hash.clone(), puzzle_content);
+
+            Self::pay_prize_pool(&puzzle_hash, &creator, &winners)?;
+            Self::award_points(&puzzle_hash, &creator, create_bn);
+            Self::close_puzzle(&puzzle_hash, &creator);
+
+            // send event
+            Self::deposit_event(Event::PuzzleSettled(puzzle_hash, current_bn));
+            //
+            Ok(().into())
+        }
+
+        // Reveal the answer, every answer is settled and may be paid. The signature check is
+        // benchmarked on its own, see `check_answer_signed`.
+        #[pallet::weight(T::WeightInfo::reveal_answers(
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ).saturating_add(T::WeightInfo::check_answer_signed()))]
+        #[transactional]
+        pub fn reveal_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            answer: Vec<u8>,
+            answer_nonce: PuzzleAnswerNonce,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            //
+            let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+            // Puzzle need exists.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+
+            // Only the creator can reveal the answer, and only once, a closed puzzle is settled.
+            ensure!(puzzle_content.account == who, Error::<T>::NotPuzzleOwner);
+            ensure!(
+                puzzle_content.answer.is_none()
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+            let current_bn: RevealBn = current_block_number.into();
+            ensure!(
+                current_bn < Self::reveal_deadline(puzzle_content.duration_bn),
+                Error::<T>::RevealPeriodExpired
+            );
+            ensure!(
+                !<PuzzleSlotsSigned<T>>::contains_key(&puzzle_hash),
+                Error::<T>::SlotAnswersRequired
+            );
+            ensure!(
+                puzzle_content.answer_nonce[..] == answer_nonce[..],
+                Error::<T>::AnswerNonceMismatch
+            );
+            ensure!(
+                Self::check_answer_signed(
+                    &who,
+                    &answer,
+                    &answer_nonce,
+                    &puzzle_content.answer_signed
+                ),
+                Error::<T>::InvalidAnswerSignature
+            );
+
+            Self::do_reveal_puzzle(&puzzle_hash, puzzle_content, &answer)?;
+            //
+            Ok(().into())
+        }
+
+        // Reveal the answer of every slot, each answer is paid by the number of slots it got
+        // right.
+        #[pallet::weight(T::WeightInfo::reveal_slot_answers(
+            T::MaxAnswerSlots::get(),
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ).saturating_add(
+            T::WeightInfo::check_answer_signed().saturating_mul(T::MaxAnswerSlots::get() as Weight)
+        ))]
+        #[transactional]
+        pub fn reveal_puzzle_slots(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            answers: Vec<Vec<u8>>,
+            answer_nonce: PuzzleAnswerNonce,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            //
+            let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+            // Puzzle need exists.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            let slots_signed =
+                <PuzzleSlotsSigned<T>>::get(&puzzle_hash).ok_or(Error::<T>::NotSlotPuzzle)?;
+
+            // Only the creator can reveal the answers, and only once, a closed puzzle is settled.
+            ensure!(puzzle_content.account == who, Error::<T>::NotPuzzleOwner);
+            ensure!(
+                puzzle_content.answer.is_none()
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+            let current_bn: RevealBn = current_block_number.into();
+            ensure!(
+                current_bn < Self::reveal_deadline(puzzle_content.duration_bn),
+                Error::<T>::RevealPeriodExpired
+            );
+            ensure!(
+                puzzle_content.answer_nonce[..] == answer_nonce[..],
+                Error::<T>::AnswerNonceMismatch
+            );
+            ensure!(
+                answers.len() == slots_signed.len(),
+                Error::<T>::SlotCountMismatch
+            );
+            ensure!(
+                answers
+                    .iter()
+                    .zip(slots_signed.iter())
+                    .all(|(answer, answer_signed)| Self::check_answer_signed(
+                        &who,
+                        answer,
+                        &answer_nonce,
+                        answer_signed
+                    )),
+                Error::<T>::InvalidAnswerSignature
+            );
+
+            Self::do_reveal_slot_puzzle(&puzzle_hash, puzzle_content, answers)?;
+            //
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::refund_puzzle(
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ))]
+        #[transactional]
+        pub fn refund_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            //
+            let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+            // Puzzle need exists, not revealed, and the reveal period is over.
+            let mut puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.answer.is_none()
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+            let current_bn: CreateBn = current_block_number.into();
+            ensure!(
+                current_bn >= Self::reveal_deadline(puzzle_content.duration_bn),
+                Error::<T>::RevealPeriodNotExpired
+            );
+
+            Self::refund_tickets(
+                &puzzle_hash,
+                &puzzle_content.account,
+                T::UnrevealedSlash::get(),
+            )?;
+            Self::close_puzzle(&puzzle_hash, &puzzle_content.account);
+
+            Self::set_puzzle_status(&puzzle_hash, &mut puzzle_content, PUZZLE_STATUS_IS_CLOSED);
+            <PuzzleInfo<T>>::insert(puzzle_hash.clone(), puzzle_content);
+
+            // send event
+            Self::deposit_event(Event::PuzzleRefunded(puzzle_hash, current_bn));
+            //
+            Ok(().into())
+        }
+
+        #[pallet::weight(T::WeightInfo::revoke_puzzle(
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ))]
+        #[transactional]
+        pub fn revoke_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            //
+            let current_block_number = <frame_system::Pallet<T>>::block_number();
+
+            // Puzzle need exists, and only the creator can revoke it before it is revealed.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(puzzle_content.account == who, Error::<T>::NotPuzzleOwner);
+            ensure!(
+                puzzle_content.answer.is_none()
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+
+            // Once someone answered, the puzzle can only be revoked before the cutoff.
+            let has_answers = <PuzzleAnswerCount<T>>::get(&puzzle_hash) > 0;
+            let current_bn: CreateBn = current_block_number.into();
+            let revoke_cutoff: CreateBn = T::RevokeCutoff::get().into();
+            ensure!(
+                !has_answers || current_bn < puzzle_content.create_bn.saturating_add(revoke_cutoff),
+                Error::<T>::RevokeCutoffPassed
+            );
+
+            Self::refund_tickets(&puzzle_hash, &who, Perbill::zero())?;
+            // a revoked puzzle is removed at once, so the reap bounty is returned as well.
+            Self::release_deposits(&puzzle_hash, &who);
+            T::Currency::unreserve(&who, <PuzzleDeposits<T>>::take(&puzzle_hash));
+
+            <PuzzleInfo<T>>::remove(&puzzle_hash);
+            <PuzzlesByCreator<T>>::remove(&who, &puzzle_hash);
+            <PuzzlesByStatus<T>>::remove(puzzle_content.puzzle_status, &puzzle_hash);
+            <PuzzleSeason<T>>::remove(&puzzle_hash);
+            for (answer_hash, answer_content) in <PuzzleAnswers<T>>::drain_prefix(&puzzle_hash) {
+                <AnswersByAccount<T>>::remove(
+                    &answer_content.account,
+                    (puzzle_hash.clone(), answer_hash),
+                );
+            }
+            <PuzzleAnswerCount<T>>::remove(&puzzle_hash);
+            <PuzzlePayouts<T>>::remove(&puzzle_hash);
+            Self::unschedule_deadline(&puzzle_hash, puzzle_content.duration_bn);
+            // unlink the revoked version, a revoked revision makes the previous version the latest.
+            if let Some(previous_hash) = <PuzzleRevisedFrom<T>>::take(&puzzle_hash) {
+                <PuzzleRevisedTo<T>>::remove(&previous_hash);
+            }
+            if let Some(next_hash) = <PuzzleRevisedTo<T>>::take(&puzzle_hash) {
+                <PuzzleRevisedFrom<T>>::remove(&next_hash);
+            }
+            for (hint_index, hint_content) in <PuzzleHints<T>>::drain_prefix(&puzzle_hash) {
+                if let (Some(unlock_bn), false) = (hint_content.unlock_bn, hint_content.unlocked) {
+                    Self::unschedule_hint_unlock(&puzzle_hash, hint_index, unlock_bn);
+                }
+            }
+            <PuzzleHintCount<T>>::remove(&puzzle_hash);
+            <HintPurchases<T>>::drain_prefix(&puzzle_hash).for_each(drop);
+            <HintDeliveries<T>>::drain_prefix(&puzzle_hash).for_each(drop);
+            <PuzzleSlotsSigned<T>>::remove(&puzzle_hash);
+            <AnswerSlotHashes<T>>::drain_prefix(&puzzle_hash).for_each(drop);
+            <PuzzleAsset<T>>::remove(&puzzle_hash);
+
+            // send event
+            Self::deposit_event(Event::PuzzleRevoked(who, puzzle_hash));
+            //
+            Ok(().into())
+        }
+
+        // Attach the commitment of a hint to a solving puzzle, see `make_hint_commitment`. The
+        // hint unlocks `unlock_after` blocks after the puzzle was created, and can be bought
+        // before that for `price`.
+        #[pallet::weight(T::WeightInfo::add_hint())]
+        #[transactional]
+        pub fn add_hint(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            commitment: HintCommitment,
+            unlock_after: Option<u64>,
+            price: Option<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let current_bn: u64 = <frame_system::Pallet<T>>::block_number().into();
+
+            // Puzzle need exists, and only the creator can add hints while it accepts answers.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(puzzle_content.account == who, Error::<T>::NotPuzzleOwner);
+            ensure!(
+                puzzle_content.puzzle_status == PUZZLE_STATUS_IS_SOLVING,
+                Error::<T>::PuzzleNotSolving
+            );
+            ensure!(
+                current_bn < puzzle_content.duration_bn,
+                Error::<T>::PuzzleUpToTime
+            );
+            ensure!(
+                unlock_after.is_some() || price.is_some(),
+                Error::<T>::HintNeverAvailable
+            );
+
+            let hint_index = <PuzzleHintCount<T>>::get(&puzzle_hash);
+            ensure!(
+                hint_index < T::MaxHintsPerPuzzle::get(),
+                Error::<T>::TooManyHints
+            );
+            Self::reserve_puzzle_deposit(
+                &who,
+                &puzzle_hash,
+                Self::bytes_deposit(commitment.len()),
+            )?;
+
+            // a hint whose unlock block number has passed can be revealed at once.
+            let unlock_bn = unlock_after
+                .map(|unlock_after| puzzle_content.create_bn.saturating_add(unlock_after));
+            let unlocked = unlock_bn.map_or(false, |unlock_bn| unlock_bn <= current_bn);
+            <PuzzleHints<T>>::insert(
+                &puzzle_hash,
+                hint_index,
+                HintData {
+                    commitment,
+                    content: None,
+                    unlock_bn,
+                    price,
+                    unlocked,
+                    purchase_count: 0,
+                },
+            );
+            <PuzzleHintCount<T>>::insert(&puzzle_hash, hint_index + 1);
+            if let (Some(unlock_bn), false) = (unlock_bn, unlocked) {
+                Self::schedule_hint_unlock(&puzzle_hash, hint_index, unlock_bn)?;
+            }
+
+            // send event
+            Self::deposit_event(Event::HintAdded(who, puzzle_hash.clone(), hint_index));
+            if unlocked {
+                Self::deposit_event(Event::HintUnlocked(puzzle_hash, hint_index, current_bn));
+            }
+            //
+            Ok(().into())
+        }
+
+        // Buy a hint before it unlocks, the price is added to the prize pool.
+        #[pallet::weight(T::WeightInfo::buy_hint())]
+        pub fn buy_hint(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            hint_index: HintIndex,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let current_bn: u64 = <frame_system::Pallet<T>>::block_number().into();
+
+            // Puzzle need exists, and still accepts answers.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.puzzle_status == PUZZLE_STATUS_IS_SOLVING,
+                Error::<T>::PuzzleNotSolving
+            );
+            ensure!(
+                current_bn < puzzle_content.duration_bn,
+                Error::<T>::PuzzleUpToTime
+            );
+
+            let mut hint_content =
+                <PuzzleHints<T>>::get(&puzzle_hash, hint_index).ok_or(Error::<T>::HintNotExist)?;
+            let price = hint_content.price.ok_or(Error::<T>::HintNotForSale)?;
+            ensure!(
+                !hint_content.unlocked
+                    && puzzle_content.account != who
+                    && !<HintPurchases<T>>::contains_key(&puzzle_hash, (hint_index, who.clone())),
+                Error::<T>::HintAlreadyAvailable
+            );
+            ensure!(
+                hint_content.purchase_count < T::MaxHintPurchases::get(),
+                Error::<T>::TooManyHintPurchases
+            );
+
+            // the hint fee is kept in the pot account with the tickets.
+            Self::pay_into_pot(&puzzle_hash, &who, price)?;
+            hint_content.purchase_count = hint_content.purchase_count.saturating_add(1);
+            <PuzzleHints<T>>::insert(&puzzle_hash, hint_index, hint_content);
+            <HintPurchases<T>>::insert(&puzzle_hash, (hint_index, who.clone()), ());
+            <PuzzlePrizePool<T>>::mutate(&puzzle_hash, |pool| *pool = pool.saturating_add(price));
+
+            // send event
+            Self::deposit_event(Event::HintPurchased(who, puzzle_hash, hint_index, price));
+            //
+            Ok(().into())
+        }
+
+        // Publish an unlocked hint, it must match the commitment given to `add_hint`.
+        #[pallet::weight(T::WeightInfo::reveal_hint(hint.len() as u32))]
+        #[transactional]
+        pub fn reveal_hint(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            hint_index: HintIndex,
+            hint: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let puzzle_content = Self::hint_puzzle(&puzzle_hash, &who)?;
+            let mut hint_content =
+                <PuzzleHints<T>>::get(&puzzle_hash, hint_index).ok_or(Error::<T>::HintNotExist)?;
+            ensure!(hint_content.unlocked, Error::<T>::HintNotUnlocked);
+            ensure!(
+                hint_content.content.is_none(),
+                Error::<T>::HintAlreadyRevealed
+            );
+            ensure!(
+                Self::make_hint_commitment(&hint) == hint_content.commitment,
+                Error::<T>::HintCommitmentMismatch
+            );
+            let content: BoundedHintOf<T> = hint.try_into().map_err(|_| Error::<T>::HintTooLong)?;
+            Self::reserve_puzzle_deposit(
+                &puzzle_content.account,
+                &puzzle_hash,
+                Self::bytes_deposit(content.len()),
+            )?;
+            hint_content.content = Some(content);
+            <PuzzleHints<T>>::insert(&puzzle_hash, hint_index, hint_content);
+
+            // send event
+            Self::deposit_event(Event::HintRevealed(puzzle_hash, hint_index));
+            //
+            Ok(().into())
+        }
+
+        // Deliver a bought hint to its buyer, `sealed_hint` is the hint encrypted to the key of
+        // the buyer, who checks it against the commitment. A purchase which is not delivered
+        // when the puzzle is settled is refunded.
+        #[pallet::weight(T::WeightInfo::deliver_hint(sealed_hint.len() as u32))]
+        #[transactional]
+        pub fn deliver_hint(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            hint_index: HintIndex,
+            buyer: T::AccountId,
+            sealed_hint: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let puzzle_content = Self::hint_puzzle(&puzzle_hash, &who)?;
+            let purchase_key = (hint_index, buyer.clone());
+            ensure!(
+                <HintPurchases<T>>::contains_key(&puzzle_hash, &purchase_key),
+                Error::<T>::HintNotPurchased
+            );
+            ensure!(
+                !<HintDeliveries<T>>::contains_key(&puzzle_hash, &purchase_key),
+                Error::<T>::HintAlreadyDelivered
+            );
+            let sealed_hint: BoundedHintOf<T> = sealed_hint
+                .try_into()
+                .map_err(|_| Error::<T>::HintTooLong)?;
+            Self::reserve_puzzle_deposit(
+                &puzzle_content.account,
+                &puzzle_hash,
+                Self::bytes_deposit(sealed_hint.len()),
+            )?;
+            <HintDeliveries<T>>::insert(&puzzle_hash, &purchase_key, sealed_hint);
+
+            // send event
+            Self::deposit_event(Event::HintDelivered(buyer, puzzle_hash, hint_index));
+            //
+            Ok(().into())
+        }
+
+        // Prune a closed puzzle once the retention period is over, the caller gets the reap
+        // bounty.
+        #[pallet::weight(Pallet::<T>::prune_puzzle_weight())]
+        pub fn reap_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let current_bn: u64 = <frame_system::Pallet<T>>::block_number().into();
+
+            // Puzzle need be closed for the retention period.
+            let closed_bn =
+                <PuzzleClosedAt<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotClosed)?;
+            let retention: u64 = T::RetentionPeriod::get().into();
+            ensure!(
+                current_bn >= closed_bn.saturating_add(retention),
+                Error::<T>::RetentionPeriodNotOver
+            );
+
+            let bounty = Self::prune_puzzle(&puzzle_hash, Some(&who));
+
+            // send event
+            Self::deposit_event(Event::PuzzleReaped(who, puzzle_hash, bounty));
+            //
+            Ok(().into())
+        }
+
+        // Take a payout in `asset_id` which could not be transferred when the puzzle was settled.
+        #[pallet::weight(T::WeightInfo::claim_payout())]
+        #[transactional]
+        pub fn claim_payout(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            asset_id: Option<T::AssetId>,
+        ) -> DispatchResultWithPostInfo {
+            // check signer
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+
+            let amount = <UnpaidPayouts<T>>::take(&who, (puzzle_hash.clone(), asset_id))
+                .ok_or(Error::<T>::NoUnpaidPayout)?;
+            Self::transfer_from_pot(asset_id, &who, amount)?;
+            // a pruned puzzle keeps its payouts in the archive, a puzzle created again in
+            // another asset has its own.
+            if <PuzzleInfo<T>>::contains_key(&puzzle_hash)
+                && <PuzzleAsset<T>>::get(&puzzle_hash) == asset_id
+            {
+                <PuzzlePayouts<T>>::mutate(&puzzle_hash, |total| {
+                    *total = total.saturating_add(amount)
+                });
+            }
+
+            // send event
+            Self::deposit_event(Event::PayoutClaimed(who, puzzle_hash, amount));
+            //
+            Ok(().into())
+        }
+
+        // Schedule a season after the last scheduled one, it starts and ends in `on_initialize`.
+        #[pallet::weight(T::WeightInfo::schedule_season())]
+        pub fn schedule_season(
+            origin: OriginFor<T>,
+            start_bn: u64,
+            end_bn: u64,
+            bonus: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::SeasonOrigin::ensure_origin(origin)?;
+
+            let current_bn: u64 = <frame_system::Pallet<T>>::block_number().into();
+            ensure!(
+                current_bn < start_bn && start_bn < end_bn,
+                Error::<T>::InvalidSeasonPeriod
+            );
+
+            let season_id = <NextSeasonId<T>>::get().max(1);
+            if let Some(last_season) = <Seasons<T>>::get(season_id - 1) {
+                ensure!(last_season.end_bn <= start_bn, Error::<T>::SeasonOverlaps);
+            }
+            <Seasons<T>>::insert(
+                season_id,
+                SeasonData {
+                    start_bn,
+                    end_bn,
+                    bonus,
+                },
+            );
+            <NextSeasonId<T>>::put(season_id + 1);
+
+            // send event
+            Self::deposit_event(Event::SeasonScheduled(season_id, start_bn, end_bn, bonus));
+            //
+            Ok(().into())
+        }
+
+        // Close a puzzle which is not settled and refund every ticket.
+        #[pallet::weight(T::WeightInfo::force_close_puzzle(
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ))]
+        #[transactional]
+        pub fn force_close_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let mut puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.puzzle_status != PUZZLE_STATUS_IS_SOLVED
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+
+            Self::refund_tickets(&puzzle_hash, &puzzle_content.account, Perbill::zero())?;
+            Self::close_puzzle(&puzzle_hash, &puzzle_content.account);
+
+            Self::set_puzzle_status(&puzzle_hash, &mut puzzle_content, PUZZLE_STATUS_IS_CLOSED);
+            <PuzzleInfo<T>>::insert(puzzle_hash.clone(), puzzle_content);
+
+            // send event
+            Self::deposit_event(Event::PuzzleForceClosed(puzzle_hash));
+            //
+            Ok(().into())
+        }
+
+        // Reveal the answer for the creator, without the signature and the reveal deadline.
+        #[pallet::weight(T::WeightInfo::force_reveal_puzzle(
+            T::MaxAnswersPerPuzzle::get(),
+            Pallet::<T>::max_hint_purchases()
+        ))]
+        #[transactional]
+        pub fn force_reveal_puzzle(
+            origin: OriginFor<T>,
+            puzzle_hash: PuzzleSubjectHash,
+            answer: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(&puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.answer.is_none()
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+            ensure!(
+                !<PuzzleSlotsSigned<T>>::contains_key(&puzzle_hash),
+                Error::<T>::SlotAnswersRequired
+            );
+
+            Self::do_reveal_puzzle(&puzzle_hash, puzzle_content, &answer)?;
+
+            // send event
+            Self::deposit_event(Event::PuzzleForceRevealed(puzzle_hash, answer));
+            //
+            Ok(().into())
+        }
+
+        // Stop or allow `who` creating puzzles.
+        #[pallet::weight(T::WeightInfo::set_blacklisted())]
+        pub fn set_blacklisted(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            blacklisted: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            if blacklisted {
+                <Blacklist<T>>::insert(&who, ());
+            } else {
+                <Blacklist<T>>::remove(&who);
+            }
+
+            // send event
+            Self::deposit_event(Event::AccountBlacklisted(who, blacklisted));
+            //
+            Ok(().into())
+        }
+
+        // Stop or allow every signed call of the pallet.
+        #[pallet::weight(T::WeightInfo::set_paused())]
+        pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            <Paused<T>>::put(paused);
+
+            // send event
+            Self::deposit_event(Event::PalletPaused(paused));
+            //
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        // The pot account which holds all puzzle and answer tickets, on top of the existential
+        // deposit which keeps it alive.
+        pub fn account_id() -> T::AccountId {
+            T::PalletId::get().into_account()
+        }
+
+        // The block number from which the creator can not reveal and the puzzle can be refunded.
+        pub fn reveal_deadline(duration_bn: DurationBn) -> DurationBn {
+            let grace_period: DurationBn = T::RevealGracePeriod::get().saturated_into();
+            duration_bn.saturating_add(grace_period)
+        }
+
+        // Return every answer ticket and hint fee, and the creator ticket except the
+        // `creator_slash` part which is split among the answerers.
+        pub fn refund_tickets(
+            puzzle_hash: &PuzzleSubjectHash,
+            creator: &T::AccountId,
+            creator_slash: Perbill,
+        ) -> DispatchResult {
+            let pool = <PuzzlePrizePool<T>>::take(puzzle_hash);
+
+            let mut refunds: Vec<(T::AccountId, BalanceOf<T>)> = Vec::new();
+            for (answer_hash, mut answer_content) in Self::answers_in_order(puzzle_hash) {
+                answer_content.answer_status = PUZZLE_ANSWER_STATUS_IS_EXPIRED;
+                refunds.push((answer_content.account.clone(), answer_content.ticket));
+                <PuzzleAnswers<T>>::insert(puzzle_hash, answer_hash, answer_content);
+            }
+
+            let mut hint_refunds: Vec<(T::AccountId, BalanceOf<T>)> =
+                <HintPurchases<T>>::iter_prefix(puzzle_hash)
+                    .filter_map(|((hint_index, buyer), _)| {
+                        <PuzzleHints<T>>::get(puzzle_hash, hint_index)
+                            .and_then(|hint_content| hint_content.price)
+                            .map(|price| (buyer, price))
+                    })
+                    .collect();
+
+            // what is left in the pool is the creator ticket. A pool which does not hold every
+            // ticket and hint fee refunds them in proportion, it never pays out more than it holds.
+            let answer_tickets = Self::total_refunds(&refunds, &hint_refunds);
+            if answer_tickets > pool {
+                let held: u128 = pool.saturated_into();
+                let owed: u128 = answer_tickets.saturated_into();
+                for (_, refund) in refunds.iter_mut().chain(hint_refunds.iter_mut()) {
+                    *refund = multiply_by_rational((*refund).saturated_into(), held, owed)
+                        .map(|scaled| scaled.saturated_into())
+                        .unwrap_or_else(|_| Zero::zero());
+                }
+            }
+            let mut creator_refund =
+                pool.saturating_sub(Self::total_refunds(&refunds, &hint_refunds));
+            if !refunds.is_empty() {
+                let answer_count: BalanceOf<T> = (refunds.len() as u32).into();
+                let compensation = (creator_slash * creator_refund) / answer_count;
+                for (_, refund) in refunds.iter_mut() {
+                    *refund = refund.saturating_add(compensation);
+                    creator_refund = creator_refund.saturating_sub(compensation);
+                }
+            }
+            refunds.extend(hint_refunds);
+            refunds.push((creator.clone(), creator_refund));
+
+            let mut total_paid: BalanceOf<T> = Zero::zero();
+            for (who, refund) in refunds {
+                if refund.is_zero() {
+                    continue;
+                }
+                if Self::pay_from_pot(puzzle_hash, &who, refund) {
+                    total_paid = total_paid.saturating_add(refund);
+                    Self::deposit_event(Event::TicketRefunded(who, puzzle_hash.clone(), refund));
+                }
+            }
+            <PuzzlePayouts<T>>::mutate(puzzle_hash, |total| {
+                *total = total.saturating_add(total_paid)
+            });
+            Ok(())
+        }
+
+        // The sum of the answer and hint refunds of `refund_tickets`.
+        fn total_refunds(
+            refunds: &[(T::AccountId, BalanceOf<T>)],
+            hint_refunds: &[(T::AccountId, BalanceOf<T>)],
+        ) -> BalanceOf<T> {
+            refunds
+                .iter()
+                .chain(hint_refunds.iter())
+                .fold(Zero::zero(), |total: BalanceOf<T>, (_, refund)| {
+                    total.saturating_add(*refund)
+                })
+        }
+
+        // The deposit for `bytes` bytes of puzzle storage.
+        fn bytes_deposit(bytes: usize) -> BalanceOf<T> {
+            T::DepositPerByte::get().saturating_mul((bytes as u32).into())
+        }
+
+        // Reserve `deposit` from the creator and add it to the puzzle deposit.
+        fn reserve_puzzle_deposit(
+            who: &T::AccountId,
+            puzzle_hash: &PuzzleSubjectHash,
+            deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::Currency::reserve(who, deposit)?;
+            <PuzzleDeposits<T>>::mutate(puzzle_hash, |total| {
+                *total = total.saturating_add(deposit)
+            });
+            Ok(())
+        }
+
+        // Return the storage deposits of a closed puzzle and of its answers, the creator
+        // deposit up to `ReapBounty` stays reserved until the puzzle is pruned.
+        fn release_deposits(puzzle_hash: &PuzzleSubjectHash, creator: &T::AccountId) {
+            let deposit = <PuzzleDeposits<T>>::take(puzzle_hash);
+            let bounty = deposit.min(T::ReapBounty::get());
+            T::Currency::unreserve(creator, deposit.saturating_sub(bounty));
+            if !bounty.is_zero() {
+                <PuzzleDeposits<T>>::insert(puzzle_hash, bounty);
+            }
+            for (answer_hash, deposit) in <AnswerDeposits<T>>::drain_prefix(puzzle_hash) {
+                if let Some(answer_content) = <PuzzleAnswers<T>>::get(puzzle_hash, &answer_hash) {
+                    T::Currency::unreserve(&answer_content.account, deposit);
+                }
+            }
+        }
+
+        // Record the answer root and the settlement of a solved or closed puzzle, release its
+        // deposits and schedule it to be pruned once the retention period is over.
+        fn close_puzzle(puzzle_hash: &PuzzleSubjectHash, creator: &T::AccountId) {
+            let answer_list = Self::answers_in_order(puzzle_hash);
+            let answer_root = Self::answer_root(&answer_list);
+            <PuzzleAnswerRoot<T>>::insert(puzzle_hash, answer_root);
+            Self::deposit_event(Event::AnswerRootRecorded(puzzle_hash.clone(), answer_root));
+
+            let current_bn: u64 = <frame_system::Pallet<T>>::block_number().saturated_into();
+            let settlement_leaf =
+                Self::settlement_leaf(puzzle_hash, &answer_root, &Self::winners(&answer_list));
+            <SettledPuzzles<T>>::append(current_bn, (puzzle_hash.clone(), settlement_leaf));
+
+            Self::release_deposits(puzzle_hash, creator);
+            Self::schedule_prune(puzzle_hash, current_bn);
+        }
+
+        // Record when a puzzle was closed and add it to `PrunableAt`.
+        pub(crate) fn schedule_prune(puzzle_hash: &PuzzleSubjectHash, closed_bn: u64) {
+            let retention: u64 = T::RetentionPeriod::get().saturated_into();
+            let mut prune_bn = closed_bn.saturating_add(retention);
+            // never schedule behind the cursor, `on_idle` would not find the puzzle.
+            let prune_cursor = <PruneCursor<T>>::get();
+            if prune_cursor == 0 {
+                <PruneCursor<T>>::put(prune_bn);
+            } else {
+                prune_bn = prune_bn.max(prune_cursor);
+            }
+            <PuzzleClosedAt<T>>::insert(puzzle_hash, closed_bn);
+            <PrunableAt<T>>::append(prune_bn, puzzle_hash.clone());
+        }
+
+        // The weight of pruning a puzzle with the most answers and hints.
+        pub fn prune_puzzle_weight() -> Weight {
+            T::WeightInfo::reap_puzzle(T::MaxAnswersPerPuzzle::get(), Self::max_hint_purchases())
+        }
+
+        // The most hint purchases of one puzzle, they are refunded or removed with the puzzle.
+        pub fn max_hint_purchases() -> u32 {
+            T::MaxHintsPerPuzzle::get().saturating_mul(T::MaxHintPurchases::get())
+        }
+
+        // Prune the puzzles of `PrunableAt` up to `now` while the weight lasts, the puzzles left
+        // over are pruned in the next blocks.
+        fn prune_closed_puzzles(now: u64, remaining_weight: Weight) -> Weight {
+            let cursor_weight = T::DbWeight::get().reads_writes(1, 1);
+            let prune_weight = Self::prune_puzzle_weight();
+            let mut used_weight = cursor_weight;
+            if used_weight > remaining_weight {
+                return 0;
+            }
+            let mut prune_cursor = <PruneCursor<T>>::get();
+            if prune_cursor == 0 {
+                return used_weight;
+            }
+
+            while prune_cursor <= now
+                && used_weight.saturating_add(cursor_weight) <= remaining_weight
+            {
+                used_weight = used_weight.saturating_add(cursor_weight);
+                let mut prune_list = <PrunableAt<T>>::get(prune_cursor);
+                while used_weight.saturating_add(prune_weight) <= remaining_weight {
+                    match prune_list.pop() {
+                        Some(puzzle_hash) => {
+                            Self::prune_puzzle(&puzzle_hash, None);
+                            used_weight = used_weight.saturating_add(prune_weight);
+                        }
+                        None => break,
+                    }
+                }
+                if !prune_list.is_empty() {
+                    <PrunableAt<T>>::insert(prune_cursor, prune_list);
+                    break;
+                }
+                <PrunableAt<T>>::remove(prune_cursor);
+                prune_cursor = prune_cursor.saturating_add(1);
+            }
+            <PruneCursor<T>>::put(prune_cursor);
+            used_weight
+        }
+
+        // Move a closed puzzle to `PuzzleArchive` and remove all of its storage. The reap
+        // bounty goes to `reaper`, or back to the creator when the puzzle is pruned in
+        // `on_idle`. Return the bounty paid.
+        fn prune_puzzle(
+            puzzle_hash: &PuzzleSubjectHash,
+            reaper: Option<&T::AccountId>,
+        ) -> BalanceOf<T> {
+            // the puzzle was reaped already.
+            let puzzle_content = match <PuzzleInfo<T>>::take(puzzle_hash) {
+                Some(puzzle_content) => puzzle_content,
+                None => return Zero::zero(),
+            };
+            let creator = puzzle_content.account.clone();
+            let closed_bn = <PuzzleClosedAt<T>>::take(puzzle_hash).unwrap_or_default();
+
+            let answer_list = Self::answers_in_order(puzzle_hash);
+            let archive_content = PuzzleArchiveData {
+                account: creator.clone(),
+                puzzle_status: puzzle_content.puzzle_status,
+                create_bn: puzzle_content.create_bn,
+                closed_bn,
+                winners: Self::winners(&answer_list),
+                answer_count: answer_list.len() as u32,
+                answer_root: <PuzzleAnswerRoot<T>>::take(puzzle_hash)
+                    .unwrap_or_else(|| Self::answer_root(&answer_list)),
+                payouts: <PuzzlePayouts<T>>::take(puzzle_hash),
+            };
+
+            <PuzzlesByCreator<T>>::remove(&creator, puzzle_hash);
+            <PuzzlesByStatus<T>>::remove(puzzle_content.puzzle_status, puzzle_hash);
+            <PuzzleSeason<T>>::remove(puzzle_hash);
+            <PuzzleRevisedTo<T>>::remove(puzzle_hash);
+            <PuzzleRevisedFrom<T>>::remove(puzzle_hash);
+            <PuzzleDirectAnswer<T>>::remove(puzzle_hash);
+            for (answer_hash, answer_content) in answer_list {
+                <PuzzleAnswers<T>>::remove(puzzle_hash, &answer_hash);
+                <AnswersByAccount<T>>::remove(
+                    &answer_content.account,
+                    (puzzle_hash.clone(), answer_hash),
+                );
+            }
+            <PuzzleAnswerCount<T>>::remove(puzzle_hash);
+            <PuzzlePrizePool<T>>::remove(puzzle_hash);
+            <PuzzleHints<T>>::drain_prefix(puzzle_hash).for_each(drop);
+            <PuzzleHintCount<T>>::remove(puzzle_hash);
+            <HintPurchases<T>>::drain_prefix(puzzle_hash).for_each(drop);
+            <HintDeliveries<T>>::drain_prefix(puzzle_hash).for_each(drop);
+            <PuzzleSlotsSigned<T>>::remove(puzzle_hash);
+            <PuzzleAsset<T>>::remove(puzzle_hash);
+            <PuzzleSlotAnswers<T>>::remove(puzzle_hash);
+            <AnswerSlotHashes<T>>::drain_prefix(puzzle_hash).for_each(drop);
+            <AnswerCorrectSlots<T>>::drain_prefix(puzzle_hash).for_each(drop);
+            <AnswerDeposits<T>>::drain_prefix(puzzle_hash).for_each(drop);
+
+            let deposit = <PuzzleDeposits<T>>::take(puzzle_hash);
+            let bounty = match reaper {
+                Some(reaper) => {
+                    let unpaid = T::Currency::repatriate_reserved(
+                        &creator,
+                        reaper,
+                        deposit,
+                        BalanceStatus::Free,
+                    )
+                    .unwrap_or(deposit);
+                    deposit.saturating_sub(unpaid)
+                }
+                None => {
+                    T::Currency::unreserve(&creator, deposit);
+                    Zero::zero()
+                }
+            };
+
+            <PuzzleArchive<T>>::insert(puzzle_hash, archive_content);
+            Self::deposit_event(Event::PuzzleArchived(puzzle_hash.clone(), closed_bn));
+            bounty
+        }
+
+        // The `merkle` leaf of an answer, `keccak_256` of the encoded
+        // `(account, answer_hash, ticket, create_bn)`.
+        pub fn answer_leaf(
+            answer_hash: &PuzzleAnswerHash,
+            answer_content: &AnswerDataOf<T>,
+        ) -> crate::merkle::MerkleHash {
+            crate::merkle::hash_leaf(
+                &(
+                    &answer_content.account,
+                    answer_hash,
+                    answer_content.ticket,
+                    answer_content.create_bn,
+                )
+                    .encode(),
+            )
+        }
+
+        // The `merkle` root of the answers of a puzzle, in the order they were submitted.
+        pub fn answer_root(
+            answer_list: &[(PuzzleAnswerHash, AnswerDataOf<T>)],
+        ) -> crate::merkle::MerkleHash {
+            let leaves: Vec<crate::merkle::MerkleHash> = answer_list
+                .iter()
+                .map(|(answer_hash, answer_content)| Self::answer_leaf(answer_hash, answer_content))
+                .collect();
+            crate::merkle::merkle_root(&leaves)
+        }
+
+        // The accounts of the correct answers, in the order they were submitted.
+        fn winners(answer_list: &[(PuzzleAnswerHash, AnswerDataOf<T>)]) -> Vec<T::AccountId> {
+            answer_list
+                .iter()
+                .filter(|(_, answer_content)| {
+                    answer_content.answer_status == PUZZLE_ANSWER_STATUS_IS_CORRECT
+                })
+                .map(|(_, answer_content)| answer_content.account.clone())
+                .collect()
+        }
+
+        // The `merkle` leaf of a settled puzzle, `keccak_256` of the encoded
+        // `(puzzle_hash, answer_root, winners)`.
+        pub fn settlement_leaf(
+            puzzle_hash: &PuzzleSubjectHash,
+            answer_root: &crate::merkle::MerkleHash,
+            winners: &[T::AccountId],
+        ) -> crate::merkle::MerkleHash {
+            crate::merkle::hash_leaf(&(puzzle_hash, answer_root, winners).encode())
+        }
+
+        // The number and the `merkle` root of the puzzles settled in the parent block, in the
+        // order they were settled. `pallet_mmr` adds the leaf of the parent block in
+        // `on_initialize`, so this is the commitment which goes into that leaf.
+        pub fn settlement_commitment() -> (u32, crate::merkle::MerkleHash) {
+            let current_bn: u64 = <frame_system::Pallet<T>>::block_number().saturated_into();
+            let settled_list = <SettledPuzzles<T>>::get(current_bn.saturating_sub(1));
+            let leaves: Vec<crate::merkle::MerkleHash> = settled_list
+                .into_iter()
+                .map(|(_, settlement_leaf)| settlement_leaf)
+                .collect();
+            (leaves.len() as u32, crate::merkle::merkle_root(&leaves))
+        }
+
+        // Change the status of a puzzle and move it in `PuzzlesByStatus`, the caller writes
+        // `puzzle_content` back.
+        fn set_puzzle_status(
+            puzzle_hash: &PuzzleSubjectHash,
+            puzzle_content: &mut PuzzleInfoDataOf<T>,
+            puzzle_status: PuzzleStatus,
+        ) {
+            <PuzzlesByStatus<T>>::remove(puzzle_content.puzzle_status, puzzle_hash);
+            <PuzzlesByStatus<T>>::insert(puzzle_status, puzzle_hash, ());
+            puzzle_content.puzzle_status = puzzle_status;
+        }
+
+        // Schedule a puzzle to be moved to "up to time" in the first block from its deadline with
+        // room, at most `MAX_SCHEDULE_DELAY` blocks late.
+        fn schedule_deadline(
+            puzzle_hash: &PuzzleSubjectHash,
+            deadline_bn: DurationBn,
+        ) -> DispatchResult {
+            for expiry_bn in deadline_bn..deadline_bn.saturating_add(MAX_SCHEDULE_DELAY) {
+                let scheduled = <PuzzleDeadlines<T>>::try_mutate(expiry_bn, |puzzle_list| {
+                    puzzle_list.try_push(puzzle_hash.clone()).map_err(|_| ())
+                });
+                if scheduled.is_ok() {
+                    return Ok(());
+                }
+            }
+            Err(Error::<T>::ScheduleFull.into())
+        }
+
+        // Remove a revoked puzzle from the block it was scheduled in.
+        fn unschedule_deadline(puzzle_hash: &PuzzleSubjectHash, deadline_bn: DurationBn) {
+            for expiry_bn in deadline_bn..deadline_bn.saturating_add(MAX_SCHEDULE_DELAY) {
+                let mut puzzle_list = <PuzzleDeadlines<T>>::get(expiry_bn);
+                if puzzle_list.contains(puzzle_hash) {
+                    puzzle_list.retain(|deadline_hash| deadline_hash != puzzle_hash);
+                    <PuzzleDeadlines<T>>::insert(expiry_bn, puzzle_list);
+                    return;
+                }
+            }
+        }
+
+        // Schedule a hint to be unlocked in the first block from its unlock block number with
+        // room, at most `MAX_SCHEDULE_DELAY` blocks late.
+        fn schedule_hint_unlock(
+            puzzle_hash: &PuzzleSubjectHash,
+            hint_index: HintIndex,
+            unlock_bn: u64,
+        ) -> DispatchResult {
+            for scheduled_bn in unlock_bn..unlock_bn.saturating_add(MAX_SCHEDULE_DELAY) {
+                let scheduled = <HintUnlocks<T>>::try_mutate(scheduled_bn, |unlock_list| {
+                    unlock_list
+                        .try_push((puzzle_hash.clone(), hint_index))
+                        .map_err(|_| ())
+                });
+                if scheduled.is_ok() {
+                    return Ok(());
+                }
+            }
+            Err(Error::<T>::ScheduleFull.into())
+        }
+
+        // Remove the hint of a revoked puzzle from the block it was scheduled in.
+        fn unschedule_hint_unlock(
+            puzzle_hash: &PuzzleSubjectHash,
+            hint_index: HintIndex,
+            unlock_bn: u64,
+        ) {
+            for scheduled_bn in unlock_bn..unlock_bn.saturating_add(MAX_SCHEDULE_DELAY) {
+                let mut unlock_list = <HintUnlocks<T>>::get(scheduled_bn);
+                let unlock_entry = (puzzle_hash.clone(), hint_index);
+                if unlock_list.contains(&unlock_entry) {
+                    unlock_list.retain(|scheduled_entry| scheduled_entry != &unlock_entry);
+                    <HintUnlocks<T>>::insert(scheduled_bn, unlock_list);
+                    return;
+                }
+            }
+        }
+
+        // Move a solving puzzle to "up to time", it does not accept answers any more. A puzzle
+        // created again under the hash of a revoked one keeps solving until its own deadline.
+        fn expire_puzzle(puzzle_hash: PuzzleSubjectHash, now: DurationBn) {
+            <PuzzleInfo<T>>::mutate(&puzzle_hash, |puzzle_content_opt| {
+                if let Some(puzzle_content) = puzzle_content_opt {
+                    if puzzle_content.puzzle_status == PUZZLE_STATUS_IS_SOLVING
+                        && puzzle_content.duration_bn <= now
+                    {
+                        Self::set_puzzle_status(
+                            &puzzle_hash,
+                            puzzle_content,
+                            PUZZLE_STATUS_IS_UP_TO_TIME,
+                        );
+                        Self::deposit_event(Event::PuzzleExpired(
+                            puzzle_hash.clone(),
+                            puzzle_content.duration_bn,
+                        ));
+                    }
+                }
+            });
+        }
+
+        // Let the creator reveal a scheduled hint.
+        fn unlock_hint(puzzle_hash: PuzzleSubjectHash, hint_index: HintIndex, now: u64) {
+            <PuzzleHints<T>>::mutate(&puzzle_hash, hint_index, |hint_content_opt| {
+                if let Some(hint_content) = hint_content_opt {
+                    let unlock_due = hint_content
+                        .unlock_bn
+                        .map_or(false, |unlock_bn| unlock_bn <= now);
+                    if !hint_content.unlocked && unlock_due {
+                        hint_content.unlocked = true;
+                        Self::deposit_event(Event::HintUnlocked(
+                            puzzle_hash.clone(),
+                            hint_index,
+                            now,
+                        ));
+                    }
+                }
+            });
+        }
+
+        // Whether `who` can read a hint: it is revealed, it was delivered to them, or they are
+        // the creator.
+        pub fn hint_available(
+            puzzle_hash: &PuzzleSubjectHash,
+            hint_index: HintIndex,
+            who: &T::AccountId,
+        ) -> bool {
+            match <PuzzleHints<T>>::get(puzzle_hash, hint_index) {
+                Some(hint_content) => {
+                    hint_content.content.is_some()
+                        || <HintDeliveries<T>>::contains_key(puzzle_hash, (hint_index, who.clone()))
+                        || <PuzzleInfo<T>>::get(puzzle_hash)
+                            .map_or(false, |puzzle_content| &puzzle_content.account == who)
+                }
+                None => false,
+            }
+        }
+
+        // The commitment of a hint which `add_hint` takes, it is `sha2_256(hint)`.
+        pub fn make_hint_commitment(hint: &[u8]) -> HintCommitment {
+            sha2_256(hint)
+        }
+
+        // The puzzle whose hint the creator `who` reveals or delivers, its answer must not be
+        // revealed yet.
+        fn hint_puzzle(
+            puzzle_hash: &PuzzleSubjectHash,
+            who: &T::AccountId,
+        ) -> Result<PuzzleInfoDataOf<T>, DispatchError> {
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(&puzzle_content.account == who, Error::<T>::NotPuzzleOwner);
+            ensure!(
+                puzzle_content.answer.is_none()
+                    && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED,
+                Error::<T>::PuzzleAlreadyRevealed
+            );
+            Ok(puzzle_content)
+        }
+
+        // The fees of the bought hints which the creator did not deliver, the buyers get them
+        // back when the puzzle is settled.
+        fn undelivered_hint_refunds(
+            puzzle_hash: &PuzzleSubjectHash,
+        ) -> Vec<(T::AccountId, BalanceOf<T>)> {
+            <HintPurchases<T>>::iter_prefix(puzzle_hash)
+                .filter(|(purchase_key, _)| {
+                    !<HintDeliveries<T>>::contains_key(puzzle_hash, purchase_key)
+                })
+                .filter_map(|((hint_index, buyer), _)| {
+                    <PuzzleHints<T>>::get(puzzle_hash, hint_index)
+                        .filter(|hint_content| hint_content.content.is_none())
+                        .and_then(|hint_content| hint_content.price)
+                        .map(|price| (buyer, price))
+                })
+                .collect()
+        }
+
+        // Check `answer_signed` is the creator signature of `answer + answer_nonce`.
+        pub fn check_answer_signed(
+            creator: &T::AccountId,
+            answer: &[u8],
+            answer_nonce: &[u8],
+            answer_signed: &[u8],
+        ) -> bool {
+            let signature = match T::Signature::decode(&mut &answer_signed[..]) {
+                Ok(signature) => signature,
+                Err(_) => return false,
+            };
+            let mut message = answer.to_vec();
+            message.extend_from_slice(answer_nonce);
+            signature.verify(&message[..], creator)
+        }
+
+        // Check and store a new puzzle, the creator ticket starts its prize pool. The version
+        // and the optional asset are passed as one pair.
+        fn do_create_puzzle(
+            who: &T::AccountId,
+            puzzle_hash: &PuzzleSubjectHash,
+            answer_signed: PuzzleAnswerSigned,
+            answer_nonce: PuzzleAnswerNonce,
+            ticket: BalanceOf<T>,
+            duration: DurationBn,
+            (puzzle_version, asset_id): (PuzzleVersion, Option<T::AssetId>),
+        ) -> DispatchResult {
+            let current_bn: CreateBn = <frame_system::Pallet<T>>::block_number().saturated_into();
+            let dration_block_number: DurationBn = duration.saturating_add(current_bn);
+
+            ensure!(
+                !<Blacklist<T>>::contains_key(who),
+                Error::<T>::CreatorBlacklisted
+            );
+            ensure!(duration >= 1, Error::<T>::InvalidDuration);
+            ensure!(
+                puzzle_hash.len() <= T::MaxHashLength::get() as usize,
+                Error::<T>::HashTooLong
+            );
+            let answer_signed: BoundedAnswerSignedOf<T> = answer_signed
+                .try_into()
+                .map_err(|_| Error::<T>::SignatureTooLong)?;
+            let answer_nonce: BoundedAnswerOf<T> = answer_nonce
+                .try_into()
+                .map_err(|_| Error::<T>::NonceTooLong)?;
+
+            ensure!(
+                !<PuzzleInfo<T>>::contains_key(puzzle_hash)
+                    && !<PuzzleArchive<T>>::contains_key(puzzle_hash),
+                Error::<T>::PuzzleAlreadyExist
+            );
+
+            // the creator ticket is kept in the pot account, the storage deposit is reserved.
+            if let Some(asset_id) = asset_id {
+                Self::fund_asset_pot(who, asset_id)?;
+                <PuzzleAsset<T>>::insert(puzzle_hash, asset_id);
+            }
+            Self::pay_into_pot(puzzle_hash, who, ticket)?;
+            let puzzle_bytes = puzzle_hash
+                .len()
+                .saturating_add(answer_signed.len())
+                .saturating_add(answer_nonce.len());
+            let deposit =
+                T::PuzzleDepositBase::get().saturating_add(Self::bytes_deposit(puzzle_bytes));
+            Self::reserve_puzzle_deposit(who, puzzle_hash, deposit)?;
+
+            let puzzle_content = PuzzleInfoData {
+                account: who.clone(),
+                answer: None,
+                answer_signed,
+                answer_nonce,
+                puzzle_ticket: ticket,
+                puzzle_status: PUZZLE_STATUS_IS_SOLVING,
+                create_bn: current_bn,
+                duration_bn: dration_block_number,
+                reveal_bn: 0,
+                puzzle_version,
+            };
+            <PuzzleInfo<T>>::insert(puzzle_hash, puzzle_content);
+            <PuzzlesByCreator<T>>::insert(who, puzzle_hash, ());
+            <PuzzlesByStatus<T>>::insert(PUZZLE_STATUS_IS_SOLVING, puzzle_hash, ());
+            if let Some(season_id) = <ActiveSeason<T>>::get() {
+                <PuzzleSeason<T>>::insert(puzzle_hash, season_id);
+            }
+            <PuzzlePrizePool<T>>::insert(puzzle_hash, ticket);
+            Self::schedule_deadline(puzzle_hash, dration_block_number)?;
+
+            // send event
+            Self::deposit_event(Event::PuzzleCreated(
+                who.clone(),
+                puzzle_hash.clone(),
+                current_bn,
+                dration_block_number,
+            ));
+            Ok(())
+        }
+
+        // Write the revealed answer and settle the answers, the prize pool is paid now or
+        // by `settle_puzzle` once the committed answers are revealed.
+        pub(crate) fn do_reveal_puzzle(
+            puzzle_hash: &PuzzleSubjectHash,
+            mut puzzle_content: PuzzleInfoDataOf<T>,
+            answer: &[u8],
+        ) -> DispatchResult {
+            let current_bn: RevealBn = <frame_system::Pallet<T>>::block_number().saturated_into();
+
+            let bounded_answer: BoundedAnswerOf<T> = answer
+                .to_vec()
+                .try_into()
+                .map_err(|_| Error::<T>::AnswerTooLong)?;
+
+            // the committed answers are revealed by the answerers before the pool is paid.
+            let answers_committed =
+                <PuzzleAnswers<T>>::iter_prefix_values(puzzle_hash).any(|answer_content| {
+                    answer_content.answer_status == PUZZLE_ANSWER_STATUS_IS_COMMITTED
+                });
+
+            // write the plaintext answer and the reveal block number.
+            puzzle_content.answer = Some(bounded_answer);
+            let puzzle_status = if answers_committed {
+                PUZZLE_STATUS_IS_ANSWER_REVEALING
+            } else {
+                PUZZLE_STATUS_IS_SOLVED
+            };
+            Self::set_puzzle_status(puzzle_hash, &mut puzzle_content, puzzle_status);
+            puzzle_content.reveal_bn = current_bn;
+            let creator = puzzle_content.account.clone();
+            let create_bn = puzzle_content.create_bn;
+            <PuzzleInfo<T>>::insert(puzzle_hash.clone(), puzzle_content);
+
+            // send event
+            Self::deposit_event(Event::PuzzleRevealed(
+                creator.clone(),
+                puzzle_hash.clone(),
+                answer.to_vec(),
+                current_bn,
+            ));
+
+            // mark the answers and send an event for each winner.
+            let winners = Self::settle_answers(puzzle_hash, answer);
+            for (winner, answer_hash) in winners.iter() {
+                Self::deposit_event(Event::AnswerSolved(
+                    winner.clone(),
+                    puzzle_hash.clone(),
+                    answer_hash.clone(),
+                ));
+            }
+
+            // pay the prize pool to the winners, or wait for `settle_puzzle`.
+            if !answers_committed {
+                let winners: Vec<T::AccountId> =
+                    winners.into_iter().map(|(winner, _)| winner).collect();
+                Self::pay_prize_pool(puzzle_hash, &creator, &winners)?;
+                Self::award_points(puzzle_hash, &creator, create_bn);
+                Self::close_puzzle(puzzle_hash, &creator);
+            }
+            Ok(())
+        }
+
+        // Write the revealed slot answers and pay every answer which got a slot right. The
+        // puzzle `answer` is set to the hash of the encoded slot answers.
+        pub(crate) fn do_reveal_slot_puzzle(
+            puzzle_hash: &PuzzleSubjectHash,
+            mut puzzle_content: PuzzleInfoDataOf<T>,
+            answers: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            let current_bn: RevealBn = <frame_system::Pallet<T>>::block_number().saturated_into();
+
+            let bounded_answers = answers
+                .iter()
+                .map(|answer| answer.clone().try_into())
+                .collect::<Result<Vec<BoundedAnswerOf<T>>, _>>()
+                .map_err(|_| Error::<T>::AnswerTooLong)?;
+            let bounded_answers: BoundedSlotAnswersOf<T> = bounded_answers
+                .try_into()
+                .map_err(|_| Error::<T>::SlotCountMismatch)?;
+            let answers_hash: BoundedAnswerOf<T> = Self::make_answer_hash(&answers.encode())
+                .try_into()
+                .map_err(|_| Error::<T>::AnswerTooLong)?;
+
+            // write the answers hash and the reveal block number.
+            puzzle_content.answer = Some(answers_hash);
+            Self::set_puzzle_status(puzzle_hash, &mut puzzle_content, PUZZLE_STATUS_IS_SOLVED);
+            puzzle_content.reveal_bn = current_bn;
+            let creator = puzzle_content.account.clone();
+            let create_bn = puzzle_content.create_bn;
+            <PuzzleInfo<T>>::insert(puzzle_hash.clone(), puzzle_content);
+            <PuzzleSlotAnswers<T>>::insert(puzzle_hash, bounded_answers);
+
+            // send event
+            Self::deposit_event(Event::PuzzleSlotsRevealed(
+                creator.clone(),
+                puzzle_hash.clone(),
+                answers.clone(),
+                current_bn,
+            ));
+
+            // mark the answers with at least one right slot as correct.
+            let revealed_hashes: Vec<PuzzleAnswerHash> = answers
+                .iter()
+                .map(|answer| Self::make_answer_hash(answer))
+                .collect();
+            let mut winners: Vec<(T::AccountId, u32)> = Vec::new();
+            for (answer_hash, mut answer_content) in Self::answers_in_order(puzzle_hash) {
+                let correct_slots = <AnswerSlotHashes<T>>::get(puzzle_hash, &answer_hash).map_or(
+                    0,
+                    |slot_hashes| {
+                        slot_hashes
+                            .iter()
+                            .zip(revealed_hashes.iter())
+                            .filter(|(slot_hash, revealed_hash)| slot_hash[..] == revealed_hash[..])
+                            .count() as u32
+                    },
+                );
+                if correct_slots > 0 {
+                    answer_content.answer_status = PUZZLE_ANSWER_STATUS_IS_CORRECT;
+                    <AnswerCorrectSlots<T>>::insert(puzzle_hash, &answer_hash, correct_slots);
+                    winners.push((answer_content.account.clone(), correct_slots));
+                    Self::deposit_event(Event::AnswerSlotsSolved(
+                        answer_content.account.clone(),
+                        puzzle_hash.clone(),
+                        answer_hash.clone(),
+                        correct_slots,
+                    ));
+                } else {
+                    answer_content.answer_status = PUZZLE_ANSWER_STATUS_IS_WRONG;
+                }
+                <PuzzleAnswers<T>>::insert(puzzle_hash, answer_hash, answer_content);
+            }
+
+            Self::pay_slot_prize_pool(puzzle_hash, &creator, &winners)?;
+            Self::award_points(puzzle_hash, &creator, create_bn);
+            Self::close_puzzle(puzzle_hash, &creator);
+            Ok(())
+        }
+
+        // Check the puzzle accepts the answer, take the ticket and store the answer.
+        fn do_answer_puzzle(
+            who: &T::AccountId,
+            puzzle_hash: &PuzzleSubjectHash,
+            answer_hash: &PuzzleAnswerHash,
+            ticket: BalanceOf<T>,
+            answer_status: PuzzleAnswerStatus,
+            slot_hashes: Option<BoundedSlotHashesOf<T>>,
+        ) -> Result<CreateBn, DispatchError> {
+            let current_bn: CreateBn = <frame_system::Pallet<T>>::block_number().saturated_into();
+
+            // Puzzle need exists, and still accepts answers.
+            let puzzle_content =
+                <PuzzleInfo<T>>::get(puzzle_hash).ok_or(Error::<T>::PuzzleNotExist)?;
+            ensure!(
+                puzzle_content.puzzle_status == PUZZLE_STATUS_IS_SOLVING,
+                Error::<T>::PuzzleNotSolving
+            );
+            ensure!(
+                current_bn < puzzle_content.duration_bn,
+                Error::<T>::PuzzleUpToTime
+            );
+            ensure!(
+                !<PuzzleRevisedTo<T>>::contains_key(puzzle_hash),
+                Error::<T>::PuzzleAlreadyRevised
+            );
+            // a slot puzzle takes an answer hash for every slot.
+            match (<PuzzleSlotsSigned<T>>::get(puzzle_hash), &slot_hashes) {
+                (None, None) => {}
+                (Some(slots_signed), Some(slot_hashes)) => ensure!(
+                    slots_signed.len() == slot_hashes.len(),
+                    Error::<T>::SlotCountMismatch
+                ),
+                (Some(_), None) => return Err(Error::<T>::SlotAnswersRequired.into()),
+                (None, Some(_)) => return Err(Error::<T>::NotSlotPuzzle.into()),
+            }
+
+            let bounded_answer_hash: BoundedAnswerHashOf<T> = answer_hash
+                .clone()
+                .try_into()
+                .map_err(|_| Error::<T>::HashTooLong)?;
+            // Determine whether the answer already exists.
+            ensure!(
+                !<PuzzleAnswers<T>>::contains_key(puzzle_hash, answer_hash),
+                Error::<T>::AnswerAlreadyExist
+            );
+            let answer_count = <PuzzleAnswerCount<T>>::get(puzzle_hash);
+            ensure!(
+                answer_count < T::MaxAnswersPerPuzzle::get(),
+                Error::<T>::TooManyAnswers
+            );
+
+            // create new answer.
+            let answer_content = AnswerData {
+                account: who.clone(),
+                answer_hash: bounded_answer_hash,
+                ticket,
+                answer_status,
+                create_bn: current_bn,
+                answer_index: answer_count,
+            };
+
+            // the answer ticket is kept in the pot account in the asset of the puzzle, the
+            // storage deposit is reserved.
+            Self::pay_into_pot(puzzle_hash, who, ticket)?;
+            let deposit = T::AnswerDeposit::get();
+            T::Currency::reserve(who, deposit)?;
+            <AnswerDeposits<T>>::insert(puzzle_hash, answer_hash, deposit);
+
+            <PuzzleAnswers<T>>::insert(puzzle_hash, answer_hash, answer_content);
+            if let Some(slot_hashes) = slot_hashes {
+                <AnswerSlotHashes<T>>::insert(puzzle_hash, answer_hash, slot_hashes);
+            }
+            <PuzzleAnswerCount<T>>::insert(puzzle_hash, answer_count + 1);
+            <AnswersByAccount<T>>::insert(who, (puzzle_hash.clone(), answer_hash.clone()), ());
+            <PuzzlePrizePool<T>>::mutate(puzzle_hash, |pool| *pool = pool.saturating_add(ticket));
+
+            Ok(current_bn)
+        }
+
+        // The versions of a puzzle from the original to the given one.
+        pub fn puzzle_version_history(puzzle_hash: &PuzzleSubjectHash) -> Vec<PuzzleSubjectHash> {
+            let mut history = sp_std::vec![puzzle_hash.clone()];
+            let mut current_hash = puzzle_hash.clone();
+            while let Some(previous_hash) = <PuzzleRevisedFrom<T>>::get(&current_hash) {
+                history.push(previous_hash.clone());
+                current_hash = previous_hash;
+            }
+            history.reverse();
+            history
+        }
+
+        // The answer hash which the players submit, it is `sha2_256(answer)`.
+        pub fn make_answer_hash(answer: &[u8]) -> PuzzleAnswerHash {
+            sha2_256(answer).to_vec()
+        }
+
+        // The commitment which the players submit with `commit_answer`,
+        // it is `sha2_256(answer + salt + account)`.
+        pub fn make_answer_commitment(
+            answer: &[u8],
+            salt: &[u8],
+            who: &T::AccountId,
+        ) -> PuzzleAnswerHash {
+            let mut message = answer.to_vec();
+            message.extend_from_slice(salt);
+            message.extend_from_slice(&who.encode());
+            sha2_256(&message).to_vec()
+        }
+
+        // The block number from which the committed answers can not be revealed any more.
+        pub fn answer_reveal_deadline(reveal_bn: RevealBn) -> RevealBn {
+            let reveal_window: RevealBn = T::AnswerRevealWindow::get().saturated_into();
+            reveal_bn.saturating_add(reveal_window)
+        }
+
+        // Mark every answer of the puzzle as correct or wrong, return the winners.
+        // The committed answers are left to `reveal_answer`.
+        pub fn settle_answers(
+            puzzle_hash: &PuzzleSubjectHash,
+            answer: &[u8],
+        ) -> Vec<(T::AccountId, PuzzleAnswerHash)> {
+            let revealed_hash = Self::make_answer_hash(answer);
+            let mut winners = Vec::new();
+            for (answer_hash, mut answer_content) in Self::answers_in_order(puzzle_hash) {
+                if answer_content.answer_status == PUZZLE_ANSWER_STATUS_IS_COMMITTED {
+                    continue;
+                }
+                if answer_hash == revealed_hash {
+                    answer_content.answer_status = PUZZLE_ANSWER_STATUS_IS_CORRECT;
+                    winners.push((answer_content.account.clone(), answer_hash.clone()));
+                } else {
+                    answer_content.answer_status = PUZZLE_ANSWER_STATUS_IS_WRONG;
+                }
+                <PuzzleAnswers<T>>::insert(puzzle_hash, answer_hash, answer_content);
+            }
+            winners
+        }
+
+        // The answers of a puzzle in the order they were submitted.
+        pub fn answers_in_order(
+            puzzle_hash: &PuzzleSubjectHash,
+        ) -> Vec<(PuzzleAnswerHash, AnswerDataOf<T>)> {
+            let mut answer_list: Vec<(PuzzleAnswerHash, AnswerDataOf<T>)> =
+                <PuzzleAnswers<T>>::iter_prefix(puzzle_hash).collect();
+            answer_list.sort_by_key(|(_, answer_content)| answer_content.answer_index);
+            answer_list
+        }
+
+        // Pay the prize pool of a solved puzzle, the creator takes the whole pool if there is no winner.
+        pub fn pay_prize_pool(
+            puzzle_hash: &PuzzleSubjectHash,
+            creator: &T::AccountId,
+            winners: &[T::AccountId],
+        ) -> DispatchResult {
+            Self::pay_pool_rewards(puzzle_hash, creator, !winners.is_empty(), |amount| {
+                let rewards = Self::split_prize(amount, winners.len() as u32);
+                winners.iter().cloned().zip(rewards).collect()
+            })
+        }
+
+        // Pay the prize pool of a solved slot puzzle, each winner by the number of slots they
+        // got right.
+        pub fn pay_slot_prize_pool(
+            puzzle_hash: &PuzzleSubjectHash,
+            creator: &T::AccountId,
+            winners: &[(T::AccountId, u32)],
+        ) -> DispatchResult {
+            Self::pay_pool_rewards(puzzle_hash, creator, !winners.is_empty(), |amount| {
+                let weights: Vec<u32> = winners.iter().map(|(_, weight)| *weight).collect();
+                let rewards = Self::split_by_weight(amount, &weights);
+                winners
+                    .iter()
+                    .map(|(winner, _)| winner.clone())
+                    .zip(rewards)
+                    .collect()
+            })
+        }
+
+        // Take the creator fee and pay the rest of the prize pool as `split_rewards` returns.
+        fn pay_pool_rewards(
+            puzzle_hash: &PuzzleSubjectHash,
+            creator: &T::AccountId,
+            has_winners: bool,
+            split_rewards: impl FnOnce(BalanceOf<T>) -> Vec<(T::AccountId, BalanceOf<T>)>,
+        ) -> DispatchResult {
+            let mut pool = <PuzzlePrizePool<T>>::take(puzzle_hash);
+            let mut total_paid: BalanceOf<T> = Zero::zero();
+            for (buyer, price) in Self::undelivered_hint_refunds(puzzle_hash) {
+                pool = pool.saturating_sub(price);
+                if Self::pay_from_pot(puzzle_hash, &buyer, price) {
+                    total_paid = total_paid.saturating_add(price);
+                    Self::deposit_event(Event::TicketRefunded(buyer, puzzle_hash.clone(), price));
+                }
+            }
+            if pool.is_zero() {
+                <PuzzlePayouts<T>>::mutate(puzzle_hash, |total| {
+                    *total = total.saturating_add(total_paid)
+                });
+                return Ok(());
+            }
+
+            let mut payouts: Vec<(T::AccountId, BalanceOf<T>)> = Vec::new();
+            if !has_winners {
+                payouts.push((creator.clone(), pool));
+            } else {
+                let fee = T::CreatorFee::get() * pool;
+                if !fee.is_zero() && Self::pay_from_pot(puzzle_hash, creator, fee) {
+                    total_paid = total_paid.saturating_add(fee);
+                    Self::deposit_event(Event::CreatorFeePaid(
+                        creator.clone(),
+                        puzzle_hash.clone(),
+                        fee,
+                    ));
+                }
+                payouts.extend(split_rewards(pool.saturating_sub(fee)));
+            }
+
+            for (winner, reward) in payouts {
+                if reward.is_zero() {
+                    continue;
+                }
+                if Self::pay_from_pot(puzzle_hash, &winner, reward) {
+                    total_paid = total_paid.saturating_add(reward);
+                    Self::deposit_event(Event::RewardPaid(winner, puzzle_hash.clone(), reward));
+                }
+            }
+            <PuzzlePayouts<T>>::mutate(puzzle_hash, |total| {
+                *total = total.saturating_add(total_paid)
+            });
+            Ok(())
+        }
+
+        fn ensure_not_paused() -> DispatchResult {
+            ensure!(!<Paused<T>>::get(), Error::<T>::PalletPaused);
+            Ok(())
+        }
+
+        // Give points to every winner of a settled puzzle by how fast it was solved, and to
+        // the creator if the puzzle has winners.
+        pub fn award_points(
+            puzzle_hash: &PuzzleSubjectHash,
+            creator: &T::AccountId,
+            create_bn: CreateBn,
+        ) {
+            // a slot puzzle gives the part of the points of the slots an answer got right.
+            let slot_count = <PuzzleSlotsSigned<T>>::get(puzzle_hash)
+                .map(|slots_signed| slots_signed.len() as u64);
+            let mut winner_count: u32 = 0;
+            for (answer_hash, answer_content) in Self::answers_in_order(puzzle_hash) {
+                if answer_content.answer_status != PUZZLE_ANSWER_STATUS_IS_CORRECT {
+                    continue;
+                }
+                winner_count += 1;
+                let solve_blocks = answer_content.create_bn.saturating_sub(create_bn);
+                let mut points = T::PointsFormula::solver_points(solve_blocks);
+                if let Some(slot_count) = slot_count {
+                    let correct_slots = <AnswerCorrectSlots<T>>::get(puzzle_hash, &answer_hash);
+                    points = points.saturating_mul(correct_slots as u64) / slot_count.max(1);
+                }
+                Self::add_points(&answer_content.account, puzzle_hash, points, |score| {
+                    score.solved_count = score.solved_count.saturating_add(1);
+                    score.total_solve_blocks =
+                        score.total_solve_blocks.saturating_add(solve_blocks);
+                });
+            }
+            if winner_count > 0 {
+                let points = T::PointsFormula::creator_points(winner_count);
+                Self::add_points(creator, puzzle_hash, points, |score| {
+                    score.created_solved_count = score.created_solved_count.saturating_add(1);
+                });
+            }
+        }
+
+        // Add the points to the all time score and the active season score of `who`.
+        fn add_points(
+            who: &T::AccountId,
+            puzzle_hash: &PuzzleSubjectHash,
+            points: u64,
+            update: impl Fn(&mut PlayerScore),
+        ) {
+            let add = |score: &mut PlayerScore| {
+                score.points = score.points.saturating_add(points);
+                update(score);
+                score.points
+            };
+            if let Some(season_id) = <PuzzleSeason<T>>::get(puzzle_hash) {
+                let season_points = <SeasonScores<T>>::mutate(season_id, who, |score| add(score));
+                <SeasonLeaderboard<T>>::mutate(season_id, |leaderboard| {
+                    Self::update_leaderboard(leaderboard, who, season_points)
+                });
+            }
+            let total_points = <PlayerScores<T>>::mutate(who, |score| add(score));
+            <Leaderboard<T>>::mutate(|leaderboard| {
+                Self::update_leaderboard(leaderboard, who, total_points)
+            });
+            Self::deposit_event(Event::PointsAwarded(
+                who.clone(),
+                puzzle_hash.clone(),
+                points,
+            ));
+        }
+
+        // Move `who` to its place for `points`, the accounts past `LeaderboardSize` drop out.
+        fn update_leaderboard(
+            leaderboard: &mut Vec<(T::AccountId, u64)>,
+            who: &T::AccountId,
+            points: u64,
+        ) {
+            leaderboard.retain(|(account, _)| account != who);
+            let position = leaderboard
+                .iter()
+                .position(|(_, board_points)| *board_points < points)
+                .unwrap_or_else(|| leaderboard.len());
+            leaderboard.insert(position, (who.clone(), points));
+            leaderboard.truncate(T::LeaderboardSize::get() as usize);
+        }
+
+        // The treasury account which pays the season bonus.
+        pub fn season_treasury() -> T::AccountId {
+            T::SeasonTreasuryId::get().into_account()
+        }
+
+        // End the active season at its `end_bn` and start the next one at its `start_bn`.
+        fn roll_seasons(now: u64) -> Weight {
+            let mut reads: Weight = 2;
+            let mut writes: Weight = 0;
+
+            if let Some(season_id) = <ActiveSeason<T>>::get() {
+                reads += 1;
+                match <Seasons<T>>::get(season_id) {
+                    Some(season) if now < season.end_bn => return T::DbWeight::get().reads(reads),
+                    Some(season) => {
+                        writes += 1;
+                        <ActiveSeason<T>>::kill();
+                        Self::deposit_event(Event::SeasonEnded(season_id, now));
+                        let (bonus_reads, bonus_writes) =
+                            Self::pay_season_bonus(season_id, season.bonus);
+                        reads += bonus_reads;
+                        writes += bonus_writes;
+                    }
+                    None => {
+                        writes += 1;
+                        <ActiveSeason<T>>::kill();
+                    }
+                }
+            }
+
+            let next_season_id = <LastStartedSeason<T>>::get().saturating_add(1);
+            reads += 1;
+            if let Some(season) = <Seasons<T>>::get(next_season_id) {
+                if season.start_bn <= now {
+                    writes += 2;
+                    <ActiveSeason<T>>::put(next_season_id);
+                    <LastStartedSeason<T>>::put(next_season_id);
+                    Self::deposit_event(Event::SeasonStarted(next_season_id, now));
+                }
+            }
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        // Split the bonus over the final leaderboard of the season, as far as the treasury can pay.
+        fn pay_season_bonus(season_id: SeasonId, bonus: BalanceOf<T>) -> (Weight, Weight) {
+            let treasury = Self::season_treasury();
+            let available =
+                T::Currency::free_balance(&treasury).saturating_sub(T::Currency::minimum_balance());
+            let bonus = bonus.min(available);
+            if bonus.is_zero() {
+                return (1, 0);
+            }
+
+            let leaderboard = <SeasonLeaderboard<T>>::get(season_id);
+            let rewards = Self::split_prize(bonus, leaderboard.len() as u32);
+            let mut writes: Weight = 0;
+            for ((account, _), reward) in leaderboard.into_iter().zip(rewards) {
+                if reward.is_zero() {
+                    continue;
+                }
+                if T::Currency::transfer(
+                    &treasury,
+                    &account,
+                    reward,
+                    ExistenceRequirement::KeepAlive,
+                )
+                .is_ok()
+                {
+                    writes += 2;
+                    Self::deposit_event(Event::SeasonBonusPaid(season_id, account, reward));
+                }
+            }
+            (2, writes)
+        }
+
+        // Split `amount` for `count` winners with the configured `PrizeDistribution`,
+        // the rounding remainder goes to the first winner.
+        pub fn split_prize(amount: BalanceOf<T>, count: u32) -> Vec<BalanceOf<T>> {
+            if count == 0 {
+                return Vec::new();
+            }
+            let mut rewards: Vec<BalanceOf<T>> = match T::PrizeDistribution::get() {
+                PrizeDistribution::EqualSplit => {
+                    let count_balance: BalanceOf<T> = count.into();
+                    let reward = amount / count_balance;
+                    (0..count).map(|_| reward).collect()
+                }
+                PrizeDistribution::FirstComeWeighted => {
+                    let total_weight: BalanceOf<T> = (count.saturating_mul(count + 1) / 2).into();
+                    (0..count)
+                        .map(|index| {
+                            let weight: BalanceOf<T> = (count - index).into();
+                            amount.saturating_mul(weight) / total_weight
+                        })
+                        .collect()
+                }
+                PrizeDistribution::WinnerTakesAll => (0..count)
+                    .map(|index| if index == 0 { amount } else { Zero::zero() })
+                    .collect(),
+            };
+            let paid = rewards
+                .iter()
+                .fold(Zero::zero(), |total: BalanceOf<T>, reward| {
+                    total.saturating_add(*reward)
+                });
+            rewards[0] = rewards[0].saturating_add(amount.saturating_sub(paid));
+            rewards
+        }
+
+        // Split `amount` by `weights`, the rounding remainder goes to the first entry.
+        pub fn split_by_weight(amount: BalanceOf<T>, weights: &[u32]) -> Vec<BalanceOf<T>> {
+            let total_weight: BalanceOf<T> = weights
+                .iter()
+                .fold(0u32, |total, weight| total.saturating_add(*weight))
+                .into();
+            if weights.is_empty() || total_weight.is_zero() {
+                return weights.iter().map(|_| Zero::zero()).collect();
+            }
+            let mut rewards: Vec<BalanceOf<T>> = weights
+                .iter()
+                .map(|weight| {
+                    let weight: BalanceOf<T> = (*weight).into();
+                    amount.saturating_mul(weight) / total_weight
+                })
+                .collect();
+            let paid = rewards
+                .iter()
+                .fold(Zero::zero(), |total: BalanceOf<T>, reward| {
+                    total.saturating_add(*reward)
+                });
+            rewards[0] = rewards[0].saturating_add(amount.saturating_sub(paid));
+            rewards
+        }
+
+        // The pot keeps the `min_balance` of every asset it holds, like the existential deposit
+        // of `Currency`. The creator of the first puzzle in an asset pays it.
+        fn fund_asset_pot(who: &T::AccountId, asset_id: T::AssetId) -> DispatchResult {
+            let pot = Self::account_id();
+            let minimum_balance =
+                <T::Assets as fungibles::Inspect<T::AccountId>>::minimum_balance(asset_id);
+            let pot_balance =
+                <T::Assets as fungibles::Inspect<T::AccountId>>::balance(asset_id, &pot);
+            if pot_balance < minimum_balance {
+                <T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+                    asset_id,
+                    who,
+                    &pot,
+                    minimum_balance.saturating_sub(pot_balance),
+                    true,
+                )?;
+            }
+            Ok(())
+        }
+
+        // Pay `amount` of the asset of a puzzle into the pot account.
+        fn pay_into_pot(
+            puzzle_hash: &PuzzleSubjectHash,
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            match <PuzzleAsset<T>>::get(puzzle_hash) {
+                Some(asset_id) => {
+                    <T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+                        asset_id,
+                        who,
+                        &Self::account_id(),
+                        amount,
+                        true,
+                    )?;
+                    Ok(())
+                }
+                None => T::Currency::transfer(
+                    who,
+                    &Self::account_id(),
+                    amount,
+                    ExistenceRequirement::KeepAlive,
+                ),
+            }
+        }
+
+        // Pay `amount` of the asset of a puzzle out of the pot account. A transfer which fails,
+        // e.g. to an account which can not hold the amount, is kept in `UnpaidPayouts` so one
+        // recipient does not block the settlement for the others. Returns if it was paid.
+        fn pay_from_pot(
+            puzzle_hash: &PuzzleSubjectHash,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> bool {
+            let asset_id = <PuzzleAsset<T>>::get(puzzle_hash);
+            if Self::transfer_from_pot(asset_id, dest, amount).is_ok() {
+                return true;
+            }
+            <UnpaidPayouts<T>>::mutate(dest, (puzzle_hash.clone(), asset_id), |unpaid| {
+                *unpaid = Some(unpaid.unwrap_or_else(Zero::zero).saturating_add(amount));
+            });
+            Self::deposit_event(Event::PayoutDeferred(
+                dest.clone(),
+                puzzle_hash.clone(),
+                amount,
+            ));
+            false
+        }
+
+        // Transfer `amount` of `asset_id`, or of `Currency` without one, out of the pot account.
+        #[transactional]
+        fn transfer_from_pot(
+            asset_id: Option<T::AssetId>,
+            dest: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            match asset_id {
+                Some(asset_id) => {
+                    <T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+                        asset_id,
+                        &Self::account_id(),
+                        dest,
+                        amount,
+                        true,
+                    )?;
+                    Ok(())
+                }
+                None => T::Currency::transfer(
+                    &Self::account_id(),
+                    dest,
+                    amount,
+                    ExistenceRequirement::KeepAlive,
+                ),
+            }
+        }
     }
 }
@@ -0,0 +1,99 @@
+// A binary Merkle tree over `keccak_256`, the hashing `pallet_mmr` uses in the runtime.
+// A node without a sibling is carried up to the next level unchanged.
+
+use sp_io::hashing::keccak_256;
+use sp_std::vec::Vec;
+
+pub type MerkleHash = [u8; 32];
+
+// The root of an empty tree.
+pub const EMPTY_ROOT: MerkleHash = [0u8; 32];
+
+pub fn hash_leaf(leaf: &[u8]) -> MerkleHash {
+    keccak_256(leaf)
+}
+
+pub fn hash_pair(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    let mut message = [0u8; 64];
+    message[..32].copy_from_slice(left);
+    message[32..].copy_from_slice(right);
+    keccak_256(&message)
+}
+
+pub fn merkle_root(leaves: &[MerkleHash]) -> MerkleHash {
+    if leaves.is_empty() {
+        return EMPTY_ROOT;
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+fn next_level(level: &[MerkleHash]) -> Vec<MerkleHash> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            if pair.len() == 2 {
+                hash_pair(&pair[0], &pair[1])
+            } else {
+                pair[0]
+            }
+        })
+        .collect()
+}
+
+// The siblings of the leaf at `index` from the bottom level up, a level where the node has
+// no sibling adds nothing.
+pub fn merkle_proof(leaves: &[MerkleHash], index: usize) -> Vec<MerkleHash> {
+    let mut proof = Vec::new();
+    if index >= leaves.len() {
+        return proof;
+    }
+    let mut level = leaves.to_vec();
+    let mut index = index;
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    proof
+}
+
+// Check `proof` leads from `leaf` at `index` of a tree of `leaf_count` leaves to `root`.
+pub fn verify_proof(
+    root: &MerkleHash,
+    leaf: &MerkleHash,
+    index: usize,
+    leaf_count: usize,
+    proof: &[MerkleHash],
+) -> bool {
+    if index >= leaf_count {
+        return false;
+    }
+    let mut node = *leaf;
+    let mut index = index;
+    let mut width = leaf_count;
+    let mut siblings = proof.iter();
+    while width > 1 {
+        let sibling = index ^ 1;
+        if sibling < width {
+            let sibling_node = match siblings.next() {
+                Some(sibling_node) => sibling_node,
+                None => return false,
+            };
+            node = if index % 2 == 0 {
+                hash_pair(&node, sibling_node)
+            } else {
+                hash_pair(sibling_node, &node)
+            };
+        }
+        index /= 2;
+        width = (width + 1) / 2;
+    }
+    siblings.next().is_none() && &node == root
+}
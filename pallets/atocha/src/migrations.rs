@@ -0,0 +1,508 @@
+// Storage migrations, called from `on_runtime_upgrade`.
+
+pub mod v2 {
+    use crate::pallet::*;
+    use codec::DecodeAll;
+    use frame_support::{
+        storage::{bounded_vec::BoundedVec, unhashed},
+        traits::Get,
+        weights::Weight,
+    };
+    use sp_runtime::traits::{SaturatedConversion, Zero};
+    use sp_std::{convert::TryInto, vec::Vec};
+
+    // The ticket was a plain u64 before it was paid with `Config::Currency`, it was never paid
+    // into the pot.
+    type LegacyTicket = u64;
+
+    type LegacyPuzzleInfo<AccountId> = (
+        AccountId,
+        PuzzleAnswerOption,
+        PuzzleAnswerSigned,
+        PuzzleAnswerNonce,
+        LegacyTicket,
+        PuzzleStatus,
+        CreateBn,
+        DurationBn,
+        RevealBn,
+        PuzzleVersion,
+    );
+
+    type LegacyAnswer<AccountId> = (
+        AccountId,
+        PuzzleAnswerHash,
+        LegacyTicket,
+        PuzzleAnswerStatus,
+        CreateBn,
+    );
+
+    // Convert the `V1` tuples of `PuzzleInfo` and `PuzzleDirectAnswer` to `PuzzleInfoData`
+    // and `AnswerData`. Every entry of a `V1` chain is a tuple. The legacy tickets become zero,
+    // the pot holds nothing for them.
+    //
+    // A puzzle which does not fit the `Config` bounds is removed with its answers. An answer
+    // with a hash which is too long is removed, and only the first `MaxAnswersPerPuzzle`
+    // answers of a puzzle are kept. What is removed is logged. A puzzle which is still solving
+    // is scheduled in `PuzzleDeadlines`, or moved to "up to time" when its deadline has passed.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        let current_bn: u64 = <frame_system::Pallet<T>>::block_number().saturated_into();
+        let puzzle_hash_list: Vec<PuzzleSubjectHash> = <PuzzleInfo<T>>::iter_keys().collect();
+        for puzzle_hash in puzzle_hash_list.iter() {
+            reads += 1;
+            let key = <PuzzleInfo<T>>::hashed_key_for(puzzle_hash);
+            let raw = match unhashed::get_raw(&key) {
+                Some(raw) => raw,
+                None => continue,
+            };
+            writes += 1;
+            match LegacyPuzzleInfo::<T::AccountId>::decode_all(&raw)
+                .ok()
+                .and_then(|legacy| convert_puzzle_info::<T>(legacy))
+            {
+                Some(mut puzzle_content) => {
+                    let (deadline_reads, deadline_writes) = schedule_deadline::<T>(
+                        puzzle_hash,
+                        &mut puzzle_content,
+                        current_bn,
+                        puzzle_hash_list.len() as u64,
+                    );
+                    reads += deadline_reads;
+                    writes += deadline_writes;
+                    <PuzzleInfo<T>>::insert(puzzle_hash, puzzle_content);
+                }
+                None => {
+                    writes += 1;
+                    log::warn!(
+                        target: "runtime::atocha",
+                        "removed puzzle {:?} which can not be converted, with its answers",
+                        puzzle_hash,
+                    );
+                    <PuzzleInfo<T>>::remove(puzzle_hash);
+                    <PuzzleDirectAnswer<T>>::remove(puzzle_hash);
+                }
+            }
+        }
+
+        let puzzle_hash_list: Vec<PuzzleSubjectHash> =
+            <PuzzleDirectAnswer<T>>::iter_keys().collect();
+        for puzzle_hash in puzzle_hash_list.iter() {
+            reads += 2;
+            let key = <PuzzleDirectAnswer<T>>::hashed_key_for(puzzle_hash);
+            let raw = match unhashed::get_raw(&key) {
+                Some(raw) => raw,
+                None => continue,
+            };
+            writes += 1;
+            let legacy_list = match Vec::<LegacyAnswer<T::AccountId>>::decode_all(&raw) {
+                Ok(legacy_list) if <PuzzleInfo<T>>::contains_key(puzzle_hash) => legacy_list,
+                Ok(_) => {
+                    log::warn!(
+                        target: "runtime::atocha",
+                        "removed the answers of puzzle {:?} which has been removed",
+                        puzzle_hash,
+                    );
+                    <PuzzleDirectAnswer<T>>::remove(puzzle_hash);
+                    continue;
+                }
+                Err(_) => {
+                    log::warn!(
+                        target: "runtime::atocha",
+                        "removed the answers of puzzle {:?} which can not be decoded",
+                        puzzle_hash,
+                    );
+                    <PuzzleDirectAnswer<T>>::remove(puzzle_hash);
+                    continue;
+                }
+            };
+            let legacy_count = legacy_list.len();
+            let answer_list = convert_answer_list::<T>(legacy_list);
+            if answer_list.len() < legacy_count {
+                log::warn!(
+                    target: "runtime::atocha",
+                    "removed {} of the {} answers of puzzle {:?} which do not fit the bounds",
+                    legacy_count - answer_list.len(),
+                    legacy_count,
+                    puzzle_hash,
+                );
+            }
+            <PuzzleDirectAnswer<T>>::insert(puzzle_hash, answer_list);
+        }
+
+        <StorageVersion<T>>::put(Releases::V2);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    fn convert_puzzle_info<T: Config>(
+        legacy: LegacyPuzzleInfo<T::AccountId>,
+    ) -> Option<PuzzleInfoDataOf<T>> {
+        let answer = match legacy.1 {
+            Some(answer) => Some(answer.try_into().ok()?),
+            None => None,
+        };
+        Some(PuzzleInfoData {
+            account: legacy.0,
+            answer,
+            answer_signed: legacy.2.try_into().ok()?,
+            answer_nonce: legacy.3.try_into().ok()?,
+            puzzle_ticket: Zero::zero(),
+            puzzle_status: legacy.5,
+            create_bn: legacy.6,
+            duration_bn: legacy.7,
+            reveal_bn: legacy.8,
+            puzzle_version: legacy.9,
+        })
+    }
+
+    // Keep the answers which fit the bounds, in the order they were submitted.
+    fn convert_answer_list<T: Config>(
+        legacy_list: Vec<LegacyAnswer<T::AccountId>>,
+    ) -> BoundedVec<AnswerDataOf<T>, T::MaxAnswersPerPuzzle> {
+        let mut answer_list = BoundedVec::default();
+        for legacy in legacy_list {
+            let answer_hash = match legacy.1.try_into() {
+                Ok(answer_hash) => answer_hash,
+                Err(_) => continue,
+            };
+            let answer_content = AnswerData {
+                account: legacy.0,
+                answer_hash,
+                ticket: Zero::zero(),
+                answer_status: legacy.3,
+                create_bn: legacy.4,
+                answer_index: answer_list.len() as u32,
+            };
+            if answer_list.try_push(answer_content).is_err() {
+                break;
+            }
+        }
+        answer_list
+    }
+
+    // The old runtime expired puzzles without `PuzzleDeadlines`. Schedule a solving puzzle in
+    // the first block from its deadline with room, or move it to "up to time" when the
+    // deadline has passed. At most `puzzle_count` puzzles are scheduled, so one of the next
+    // `puzzle_count` blocks has room. Returns the reads and writes.
+    fn schedule_deadline<T: Config>(
+        puzzle_hash: &PuzzleSubjectHash,
+        puzzle_content: &mut PuzzleInfoDataOf<T>,
+        current_bn: u64,
+        puzzle_count: u64,
+    ) -> (Weight, Weight) {
+        if puzzle_content.puzzle_status != PUZZLE_STATUS_IS_SOLVING {
+            return (0, 0);
+        }
+        let mut reads: Weight = 0;
+        if puzzle_content.duration_bn > current_bn {
+            let deadline_bn = puzzle_content.duration_bn;
+            for expiry_bn in deadline_bn..deadline_bn.saturating_add(puzzle_count) {
+                reads += 1;
+                let scheduled = <PuzzleDeadlines<T>>::try_mutate(expiry_bn, |puzzle_list| {
+                    puzzle_list.try_push(puzzle_hash.clone()).map_err(|_| ())
+                });
+                if scheduled.is_ok() {
+                    return (reads, 1);
+                }
+            }
+            log::warn!(
+                target: "runtime::atocha",
+                "puzzle {:?} could not be scheduled, it is up to time",
+                puzzle_hash,
+            );
+        }
+        puzzle_content.puzzle_status = PUZZLE_STATUS_IS_UP_TO_TIME;
+        (reads, 0)
+    }
+}
+
+pub mod v3 {
+    use crate::pallet::*;
+    use frame_support::{traits::Get, weights::Weight};
+
+    // Move every answer list of `PuzzleDirectAnswer` into `PuzzleAnswers`, `PuzzleAnswerCount`
+    // and `AnswersByAccount`.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        for (puzzle_hash, answer_list) in <PuzzleDirectAnswer<T>>::drain() {
+            reads += 1;
+            writes += 2;
+            <PuzzleAnswerCount<T>>::insert(&puzzle_hash, answer_list.len() as u32);
+            for answer_content in answer_list.into_inner() {
+                writes += 2;
+                let answer_hash = answer_content.answer_hash.to_vec();
+                <AnswersByAccount<T>>::insert(
+                    &answer_content.account,
+                    (puzzle_hash.clone(), answer_hash.clone()),
+                    (),
+                );
+                <PuzzleAnswers<T>>::insert(&puzzle_hash, answer_hash, answer_content);
+            }
+        }
+
+        <StorageVersion<T>>::put(Releases::V3);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+pub mod v4 {
+    use crate::pallet::*;
+    use frame_support::{traits::Get, weights::Weight};
+
+    // Index every puzzle in `PuzzlesByCreator`, and rebuild `AnswersByAccount` from
+    // `PuzzleAnswers` so both indexes match the stored puzzles and answers.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        for (puzzle_hash, puzzle_content) in <PuzzleInfo<T>>::iter() {
+            reads += 1;
+            writes += 1;
+            <PuzzlesByCreator<T>>::insert(&puzzle_content.account, puzzle_hash, ());
+        }
+
+        for (puzzle_hash, answer_hash, answer_content) in <PuzzleAnswers<T>>::iter() {
+            reads += 1;
+            writes += 1;
+            <AnswersByAccount<T>>::insert(&answer_content.account, (puzzle_hash, answer_hash), ());
+        }
+
+        <StorageVersion<T>>::put(Releases::V4);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+pub mod v5 {
+    use crate::pallet::*;
+    use frame_support::{traits::Get, weights::Weight};
+    use sp_runtime::traits::SaturatedConversion;
+
+    // Schedule the puzzles which were solved or closed before the upgrade for pruning. A
+    // solved puzzle was closed at its reveal block number, the others at the upgrade.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        let current_bn: u64 = <frame_system::Pallet<T>>::block_number().saturated_into();
+        for (puzzle_hash, puzzle_content) in <PuzzleInfo<T>>::iter() {
+            reads += 1;
+            if puzzle_content.puzzle_status != PUZZLE_STATUS_IS_SOLVED
+                && puzzle_content.puzzle_status != PUZZLE_STATUS_IS_CLOSED
+            {
+                continue;
+            }
+            reads += 1;
+            writes += 3;
+            let closed_bn = if puzzle_content.reveal_bn > 0 {
+                puzzle_content.reveal_bn
+            } else {
+                current_bn
+            };
+            <Pallet<T>>::schedule_prune(&puzzle_hash, closed_bn);
+        }
+
+        <StorageVersion<T>>::put(Releases::V5);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+pub mod v6 {
+    use crate::pallet::*;
+    use frame_support::{
+        traits::{Currency, Get},
+        weights::Weight,
+    };
+
+    // Fund the pot account with the existential deposit. Before, the payout which emptied the
+    // pot could reap it together with the tickets of the other puzzles.
+    pub fn migrate<T: Config>() -> Weight {
+        let pot = <Pallet<T>>::account_id();
+        let minimum_balance = T::Currency::minimum_balance();
+        let free_balance = T::Currency::free_balance(&pot);
+        if free_balance < minimum_balance {
+            let _ = T::Currency::deposit_creating(&pot, minimum_balance - free_balance);
+        }
+
+        <StorageVersion<T>>::put(Releases::V6);
+        T::DbWeight::get().reads_writes(2, 2)
+    }
+}
+
+pub mod v7 {
+    use crate::pallet::*;
+    use frame_support::{traits::Get, weights::Weight};
+
+    // Index every puzzle in `PuzzlesByStatus`.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        for (puzzle_hash, puzzle_content) in <PuzzleInfo<T>>::iter() {
+            reads += 1;
+            writes += 1;
+            <PuzzlesByStatus<T>>::insert(puzzle_content.puzzle_status, puzzle_hash, ());
+        }
+
+        <StorageVersion<T>>::put(Releases::V7);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+pub mod v8 {
+    use crate::pallet::*;
+    use codec::DecodeAll;
+    use frame_support::{storage::unhashed, traits::Get, weights::Weight};
+    use sp_std::vec::Vec;
+
+    // `AnswerData` before it had `answer_index`.
+    type LegacyAnswerData<T> = (
+        <T as frame_system::Config>::AccountId,
+        BoundedAnswerHashOf<T>,
+        BalanceOf<T>,
+        PuzzleAnswerStatus,
+        CreateBn,
+    );
+
+    // Number the answers of every puzzle in `AnswerData::answer_index`. The submission order
+    // was not stored, the answers are numbered by block number and then by storage order. An
+    // answer which already has its index, written by `v3` in the same upgrade, is kept, the
+    // two layouts differ in length so an entry decodes as only one of them.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        let answer_keys: Vec<(PuzzleSubjectHash, PuzzleAnswerHash)> =
+            <PuzzleAnswers<T>>::iter_keys().collect();
+        let mut legacy_answers = Vec::new();
+        for (puzzle_hash, answer_hash) in answer_keys {
+            reads += 1;
+            let key = <PuzzleAnswers<T>>::hashed_key_for(&puzzle_hash, &answer_hash);
+            let raw = match unhashed::get_raw(&key) {
+                Some(raw) => raw,
+                None => continue,
+            };
+            if AnswerDataOf::<T>::decode_all(&raw).is_ok() {
+                continue;
+            }
+            match LegacyAnswerData::<T>::decode_all(&raw) {
+                Ok(legacy) => legacy_answers.push((puzzle_hash, answer_hash, legacy)),
+                Err(_) => {
+                    writes += 1;
+                    log::warn!(
+                        target: "runtime::atocha",
+                        "removed the answer {:?} of puzzle {:?} which can not be decoded",
+                        answer_hash,
+                        puzzle_hash,
+                    );
+                    <PuzzleAnswers<T>>::remove(&puzzle_hash, &answer_hash);
+                }
+            }
+        }
+
+        // the sort is stable, so the answers of a block keep their storage order.
+        legacy_answers.sort_by(|(hash_a, _, legacy_a), (hash_b, _, legacy_b)| {
+            (hash_a, legacy_a.4).cmp(&(hash_b, legacy_b.4))
+        });
+        let mut answer_index: u32 = 0;
+        let mut previous_hash: Option<PuzzleSubjectHash> = None;
+        for (puzzle_hash, answer_hash, legacy) in legacy_answers {
+            if previous_hash.as_ref() != Some(&puzzle_hash) {
+                answer_index = 0;
+                previous_hash = Some(puzzle_hash.clone());
+            }
+            writes += 1;
+            <PuzzleAnswers<T>>::insert(
+                &puzzle_hash,
+                &answer_hash,
+                AnswerData {
+                    account: legacy.0,
+                    answer_hash: legacy.1,
+                    ticket: legacy.2,
+                    answer_status: legacy.3,
+                    create_bn: legacy.4,
+                    answer_index,
+                },
+            );
+            answer_index += 1;
+        }
+
+        <StorageVersion<T>>::put(Releases::V8);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+}
+
+pub mod v9 {
+    use crate::pallet::*;
+    use codec::{Decode, DecodeAll};
+    use frame_support::{
+        storage::{unhashed, StoragePrefixedMap},
+        traits::Get,
+        weights::Weight,
+    };
+    use sp_runtime::traits::{Saturating, Zero};
+    use sp_std::vec::Vec;
+
+    // `UnpaidPayouts` before it was keyed by the asset, under `(account, puzzle_hash)`.
+    type LegacyUnpaidPayout<T> = (Option<<T as Config>::AssetId>, BalanceOf<T>);
+
+    // Move every unpaid payout to the key of its asset. Both keys are `Blake2_128Concat`, a
+    // legacy key ends with the encoded puzzle hash and a new one with the encoded asset id.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut reads: Weight = 1;
+        let mut writes: Weight = 1;
+
+        let prefix = <UnpaidPayouts<T>>::final_prefix();
+        let mut legacy_payouts = Vec::new();
+        let mut previous_key = prefix.to_vec();
+        while let Some(key) = sp_io::storage::next_key(&previous_key) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            reads += 1;
+            if let Some((account, puzzle_hash)) = decode_legacy_key::<T>(&key[prefix.len()..]) {
+                let payout = unhashed::get_raw(&key)
+                    .and_then(|raw| LegacyUnpaidPayout::<T>::decode_all(&raw).ok());
+                legacy_payouts.push((key.clone(), account, puzzle_hash, payout));
+            }
+            previous_key = key;
+        }
+
+        for (key, account, puzzle_hash, payout) in legacy_payouts {
+            writes += 1;
+            unhashed::kill(&key);
+            match payout {
+                Some((asset_id, amount)) => {
+                    writes += 1;
+                    <UnpaidPayouts<T>>::mutate(&account, (puzzle_hash, asset_id), |unpaid| {
+                        *unpaid = Some(unpaid.unwrap_or_else(Zero::zero).saturating_add(amount));
+                    });
+                }
+                None => log::warn!(
+                    target: "runtime::atocha",
+                    "removed the unpaid payout of puzzle {:?} which can not be decoded",
+                    puzzle_hash,
+                ),
+            }
+        }
+
+        <StorageVersion<T>>::put(Releases::V9);
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    // The account and puzzle hash of a legacy key, after the storage prefix.
+    fn decode_legacy_key<T: Config>(mut key: &[u8]) -> Option<(T::AccountId, PuzzleSubjectHash)> {
+        // `Blake2_128Concat` puts a 16 byte hash before each encoded key.
+        key = key.get(16..)?;
+        let account = T::AccountId::decode(&mut key).ok()?;
+        key = key.get(16..)?;
+        let puzzle_hash = PuzzleSubjectHash::decode(&mut key).ok()?;
+        if key.is_empty() {
+            Some((account, puzzle_hash))
+        } else {
+            None
+        }
+    }
+}
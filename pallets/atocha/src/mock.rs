@@ -1,8 +1,10 @@
 use crate as pallet_atocha;
 use sp_core::H256;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::{GenesisBuild, Get}, PalletId};
+use std::cell::RefCell;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, UintAuthorityId},
+	Perbill,
 };
 use frame_system as system;
 
@@ -17,7 +19,9 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		AtochaModule: pallet_atocha::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		AtochaModule: pallet_atocha::{Pallet, Call, Storage, Config, Event<T>},
 	}
 );
 
@@ -44,7 +48,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -52,11 +56,143 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: u64 = 1;
+	pub const ApprovalDeposit: u64 = 1;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = system::EnsureRoot<u64>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = StringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AtochaPalletId: PalletId = PalletId(*b"py/atoch");
+	pub const SeasonTreasuryId: PalletId = PalletId(*b"py/atsea");
+	pub const CreatorFee: Perbill = Perbill::from_percent(10);
+	pub const AtochaPrizeDistribution: pallet_atocha::PrizeDistribution =
+		pallet_atocha::PrizeDistribution::EqualSplit;
+	pub const MaxExpiriesPerBlock: u32 = 2;
+	pub const RevealGracePeriod: u64 = 10;
+	pub const UnrevealedSlash: Perbill = Perbill::from_percent(50);
+	pub const RevokeCutoff: u64 = 20;
+	pub const AnswerRevealWindow: u64 = 10;
+	pub const MaxHashLength: u32 = 64;
+	pub const MaxAnswerLength: u32 = 64;
+	pub const MaxSignatureLength: u32 = 128;
+	pub const MaxAnswersPerPuzzle: u32 = 3;
+	pub const PuzzleDepositBase: u64 = 10;
+	pub const DepositPerByte: u64 = 1;
+	pub const AnswerDeposit: u64 = 5;
+	pub const MaxAnswerSlots: u32 = 3;
+	pub const MaxHintsPerPuzzle: u32 = 2;
+	pub const MaxHintPurchases: u32 = 2;
+	pub const MaxHintLength: u32 = 64;
+	pub const LeaderboardSize: u32 = 2;
+	pub const RetentionPeriod: u64 = 100;
+}
+
+thread_local! {
+	pub static REAP_BOUNTY: RefCell<u64> = RefCell::new(0);
+}
+
+// The reap bounty is 0 unless a test sets `REAP_BOUNTY`, so the other tests get their whole
+// deposit back when a puzzle is closed.
+pub struct ReapBounty;
+impl Get<u64> for ReapBounty {
+	fn get() -> u64 {
+		REAP_BOUNTY.with(|bounty| *bounty.borrow())
+	}
+}
+
+// 100 points for a solve in the creation block, one point less for every later block.
+pub struct TestPointsFormula;
+impl pallet_atocha::PointsFormula for TestPointsFormula {
+	fn solver_points(solve_blocks: u64) -> u64 {
+		100 - solve_blocks.min(50)
+	}
+
+	fn creator_points(winner_count: u32) -> u64 {
+		10 * winner_count as u64
+	}
+}
+
 impl pallet_atocha::Config for Test {
 	type Event = Event;
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+	type Currency = Balances;
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = AtochaPalletId;
+	type CreatorFee = CreatorFee;
+	type PrizeDistribution = AtochaPrizeDistribution;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type RevealGracePeriod = RevealGracePeriod;
+	type UnrevealedSlash = UnrevealedSlash;
+	type RevokeCutoff = RevokeCutoff;
+	type AnswerRevealWindow = AnswerRevealWindow;
+	type MaxHashLength = MaxHashLength;
+	type MaxAnswerLength = MaxAnswerLength;
+	type MaxSignatureLength = MaxSignatureLength;
+	type MaxAnswersPerPuzzle = MaxAnswersPerPuzzle;
+	type PuzzleDepositBase = PuzzleDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type AnswerDeposit = AnswerDeposit;
+	type RetentionPeriod = RetentionPeriod;
+	type ReapBounty = ReapBounty;
+	type MaxAnswerSlots = MaxAnswerSlots;
+	type MaxHintsPerPuzzle = MaxHintsPerPuzzle;
+	type MaxHintPurchases = MaxHintPurchases;
+	type MaxHintLength = MaxHintLength;
+	type PointsFormula = TestPointsFormula;
+	type LeaderboardSize = LeaderboardSize;
+	type AdminOrigin = system::EnsureRoot<u64>;
+	type SeasonOrigin = system::EnsureRoot<u64>;
+	type SeasonTreasuryId = SeasonTreasuryId;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1_000_000), (2, 1_000_000), (3, 1_000_000), (4, 1_000_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_atocha::GenesisConfig, &mut t).unwrap();
+	t.into()
 }
@@ -1,7 +1,15 @@
 use super::Event as AtochaEvent;
 use crate::pallet::*;
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{merkle, mock::*, Error};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{unhashed, StoragePrefixedMap},
+    traits::{Currency, OnIdle, OnInitialize, OnRuntimeUpgrade},
+    Blake2_128Concat, StorageHasher,
+};
+use sp_runtime::{testing::TestSignature, DispatchError};
+use sp_std::convert::TryInto;
 
 const CONST_ORIGIN_IS_CREATOR: u64 = 1;
 const CONST_ORIGIN_IS_ANSWER_1: u64 = 2;
@@ -22,27 +30,27 @@ fn test_create_puzzle() {
             50,
         );
 
-        // (PuzzleSubjectHash, PuzzleAnswerHash, PuzzleTicket, PuzzleRelationType, PuzzleStatus, u64, u64 )
         let relation_info = AtochaModule::puzzle_info(toVec("PUZZLE_HASH")).unwrap();
         // println!("==== {:?}", relation_info);
-        // if let (_, _, puzzle_hash, _, _, _, _, _, _, _) = relation_info {
-        //     println!("puzzle_hash = {:?}", sp_std::str::from_utf8(&puzzle_hash));
-        // };
 
         assert_eq!(
             relation_info,
-            (
-                CONST_ORIGIN_IS_CREATOR,
-                None,
-                toVec("ANSWER_SIGNED"), //.as_bytes().to_vec(),
-                toVec("NONCE"),         //.as_bytes().to_vec(),
-                10,
-                PUZZLE_STATUS_IS_SOLVING,
-                5,
-                5 + 50,
-                0,
-                1,
-            )
+            PuzzleInfoData {
+                account: CONST_ORIGIN_IS_CREATOR,
+                answer: None,
+                answer_signed: toVec("ANSWER_SIGNED").try_into().unwrap(),
+                answer_nonce: toVec("NONCE").try_into().unwrap(),
+                puzzle_ticket: 10,
+                puzzle_status: PUZZLE_STATUS_IS_SOLVING,
+                create_bn: 5,
+                duration_bn: 5 + 50,
+                reveal_bn: 0,
+                puzzle_version: 1,
+            }
+        );
+        assert!(
+            AtochaModule::puzzles_by_creator(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH"))
+                .is_some()
         );
         //
         System::assert_last_event(
@@ -63,8 +71,7 @@ fn test_answer_puzzle() {
         System::set_block_number(5);
 
         // check initial status.
-        let answer_list = AtochaModule::puzzle_direct_answer(&toVec("PUZZLE_HASH"));
-        assert_eq!(None, answer_list);
+        assert_eq!(0, AtochaModule::puzzle_answer_count(&toVec("PUZZLE_HASH")));
 
         // if puzzle not exists.
         assert_noop!(
@@ -100,25 +107,36 @@ fn test_answer_puzzle() {
             500,
         ));
 
-        // check answer list count.
-        let answer_list = AtochaModule::puzzle_direct_answer(&toVec("PUZZLE_HASH"));
-        assert!(answer_list.is_some());
-        if let Some(answer_list) = answer_list {
-            // println!("answer_list = {:?}", answer_list);
-            assert_eq!(1, answer_list.len());
-            // check list item
-            // Vec<(
-            //     T::AccountId,
-            //     PuzzleAnswerHash,
-            //     PuzzleTicket,
-            //     PuzzleAnswerStatus,
-            //     CreateBn,
-            // )>,
-            assert_eq!(
-                (CONST_ORIGIN_IS_ANSWER_1, toVec("ANSWER_HASH"), 500, 0, 5),
-                answer_list[0]
-            );
-        }
+        // check answer count.
+        assert_eq!(1, AtochaModule::puzzle_answer_count(&toVec("PUZZLE_HASH")));
+        // check answer item
+        assert_eq!(
+            Some(AnswerData {
+                account: CONST_ORIGIN_IS_ANSWER_1,
+                answer_hash: toVec("ANSWER_HASH").try_into().unwrap(),
+                ticket: 500,
+                answer_status: PUZZLE_ANSWER_STATUS_IS_PENDING,
+                create_bn: 5,
+                answer_index: 0,
+            }),
+            AtochaModule::puzzle_answers(&toVec("PUZZLE_HASH"), &toVec("ANSWER_HASH"))
+        );
+        assert!(AtochaModule::answers_by_account(
+            CONST_ORIGIN_IS_ANSWER_1,
+            (toVec("PUZZLE_HASH"), toVec("ANSWER_HASH"))
+        )
+        .is_some());
+
+        // the same answer hash can not be submitted twice.
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_HASH"),
+                500,
+            ),
+            Error::<Test>::AnswerAlreadyExist
+        );
     });
 }
 
@@ -128,8 +146,13 @@ fn test_reveal_puzzle() {
         System::set_block_number(5);
         //
         use sp_application_crypto::sr25519;
+        use sp_core::crypto::{AccountId32 as AccountId, Ss58Codec};
+        use sp_core::sr25519::{Public, Signature};
+        use sp_core::Pair;
+        use sp_runtime::traits::{IdentifyAccount, Verify};
         use sp_runtime::MultiSignature;
         use sp_runtime::MultiSigner;
+        use sp_std::convert::TryFrom;
 
         // sp_core::sr25519::Pair(schnorrkel::Keypair).;
 
@@ -177,19 +200,3183 @@ fn test_reveal_puzzle() {
     });
 }
 
+#[test]
+fn test_reveal_puzzle_with_answer_signed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        // the creator signs `answer + nonce`.
+        let answer_signed =
+            TestSignature(CONST_ORIGIN_IS_CREATOR, toVec("ANSWER_TEXTNONCE")).encode();
+        assert_ok!(AtochaModule::create_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            answer_signed.clone(),
+            toVec("NONCE"),
+            10,
+            50,
+            None,
+        ));
+
+        System::set_block_number(15);
+
+        // only the creator can reveal.
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("NONCE"),
+            ),
+            Error::<Test>::NotPuzzleOwner
+        );
+        // the nonce must be the one given on creation.
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("OTHER_NONCE"),
+            ),
+            Error::<Test>::AnswerNonceMismatch
+        );
+        // the answer must match the signature.
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("WRONG_TEXT"),
+                toVec("NONCE"),
+            ),
+            Error::<Test>::InvalidAnswerSignature
+        );
+
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+
+        let relation_info = AtochaModule::puzzle_info(toVec("PUZZLE_HASH")).unwrap();
+        assert_eq!(
+            relation_info.answer,
+            Some(toVec("ANSWER_TEXT").try_into().unwrap())
+        );
+        assert_eq!(relation_info.reveal_bn, 15);
+        assert_has_event(
+            AtochaEvent::PuzzleRevealed(
+                CONST_ORIGIN_IS_CREATOR,
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                15,
+            )
+            .into(),
+        );
+
+        // can not reveal twice.
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("NONCE"),
+            ),
+            Error::<Test>::PuzzleAlreadyRevealed
+        );
+    });
+}
+
+#[test]
+fn test_reveal_puzzle_settle_answers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            10,
+            50,
+        );
+
+        let correct_hash = AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT"));
+        let wrong_hash = AtochaModule::make_answer_hash(&toVec("WRONG_TEXT"));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            wrong_hash.clone(),
+            500,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            correct_hash.clone(),
+            500,
+        ));
+
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+
+        let relation_info = AtochaModule::puzzle_info(toVec("PUZZLE_HASH")).unwrap();
+        assert_eq!(relation_info.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+
+        assert_eq!(
+            Some(AnswerData {
+                account: CONST_ORIGIN_IS_ANSWER_1,
+                answer_hash: wrong_hash.clone().try_into().unwrap(),
+                ticket: 500,
+                answer_status: PUZZLE_ANSWER_STATUS_IS_WRONG,
+                create_bn: 5,
+                answer_index: 0,
+            }),
+            AtochaModule::puzzle_answers(&toVec("PUZZLE_HASH"), &wrong_hash)
+        );
+        assert_eq!(
+            Some(AnswerData {
+                account: CONST_ORIGIN_IS_ANSWER_2,
+                answer_hash: correct_hash.clone().try_into().unwrap(),
+                ticket: 500,
+                answer_status: PUZZLE_ANSWER_STATUS_IS_CORRECT,
+                create_bn: 5,
+                answer_index: 1,
+            }),
+            AtochaModule::puzzle_answers(&toVec("PUZZLE_HASH"), &correct_hash)
+        );
+        assert_has_event(
+            AtochaEvent::AnswerSolved(
+                CONST_ORIGIN_IS_ANSWER_2,
+                toVec("PUZZLE_HASH"),
+                correct_hash.clone(),
+            )
+            .into(),
+        );
+
+        // the puzzle does not accept answers any more.
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH"),
+                correct_hash,
+                500,
+            ),
+            Error::<Test>::PuzzleNotSolving
+        );
+    });
+}
+
+#[test]
+fn test_tickets_are_paid_into_pot() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            10,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_HASH"),
+            500,
+        ));
+
+        // the storage deposits are reserved: 10 + 29 bytes for the puzzle, 5 for the answer.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            1_000_000 - 10 - 39
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 500 - 5
+        );
+        // the pot holds the existential deposit from the genesis.
+        assert_eq!(
+            Balances::free_balance(AtochaModule::account_id()),
+            1 + 10 + 500
+        );
+
+        // the ticket can not be more than the free balance.
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+                toVec("PUZZLE_HASH"),
+                toVec("OTHER_ANSWER_HASH"),
+                2_000_000,
+            ),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn test_reveal_puzzle_pay_prize_pool() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("WRONG_TEXT")),
+            400,
+        ));
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 1000);
+
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+
+        // 10% creator fee, the rest goes to the only winner.
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_CREATOR), 1_000_000);
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 500 + 900
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_2),
+            1_000_000 - 400
+        );
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 0);
+        assert_eq!(AtochaModule::puzzle_payouts(toVec("PUZZLE_HASH")), 1000);
+        assert_has_event(
+            AtochaEvent::CreatorFeePaid(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH"), 100).into(),
+        );
+        assert_has_event(
+            AtochaEvent::RewardPaid(CONST_ORIGIN_IS_ANSWER_1, toVec("PUZZLE_HASH"), 900).into(),
+        );
+    });
+}
+
+#[test]
+fn test_reveal_puzzle_without_winner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("WRONG_TEXT")),
+            500,
+        ));
+
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+
+        // nobody solved the puzzle, the creator takes the whole pool.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            1_000_000 + 500
+        );
+        assert_eq!(AtochaModule::puzzle_payouts(toVec("PUZZLE_HASH")), 600);
+        assert_has_event(
+            AtochaEvent::RewardPaid(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH"), 600).into(),
+        );
+    });
+}
+
+#[test]
+fn test_split_prize() {
+    new_test_ext().execute_with(|| {
+        // the rounding remainder goes to the first winner.
+        assert_eq!(AtochaModule::split_prize(100, 3), vec![34, 33, 33]);
+        assert_eq!(AtochaModule::split_prize(100, 0), Vec::<u64>::new());
+        assert_eq!(AtochaModule::split_by_weight(100, &[2, 1]), vec![67, 33]);
+    });
+}
+
+#[test]
+fn test_puzzle_expired_on_initialize() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_1",
+            "ANSWER_SIGNED",
+            "NONCE",
+            10,
+            50,
+        );
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "ANSWER_SIGNED",
+            "NONCE",
+            10,
+            50,
+        );
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_3",
+            "ANSWER_SIGNED",
+            "NONCE",
+            10,
+            50,
+        );
+
+        // at most 2 puzzles expire in one block, the last one waits for the next block.
+        assert_eq!(AtochaModule::puzzle_deadlines(55).len(), 2);
+        assert_eq!(
+            AtochaModule::puzzle_deadlines(56).into_inner(),
+            vec![toVec("PUZZLE_HASH_3")]
+        );
+        System::set_block_number(55);
+        AtochaModule::on_initialize(55);
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH_1"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_UP_TO_TIME
+        );
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH_2"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_UP_TO_TIME
+        );
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH_3"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_SOLVING
+        );
+        assert_eq!(AtochaModule::puzzle_deadlines(55).len(), 0);
+        assert_eq!(AtochaModule::puzzle_deadlines(56).len(), 1);
+        assert_has_event(AtochaEvent::PuzzleExpired(toVec("PUZZLE_HASH_1"), 55).into());
+
+        // the puzzle does not accept answers after the deadline.
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH_1"),
+                toVec("ANSWER_HASH"),
+                500,
+            ),
+            Error::<Test>::PuzzleNotSolving
+        );
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH_3"),
+                toVec("ANSWER_HASH"),
+                500,
+            ),
+            Error::<Test>::PuzzleUpToTime
+        );
+
+        System::set_block_number(56);
+        AtochaModule::on_initialize(56);
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH_3"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_UP_TO_TIME
+        );
+    });
+}
+
+fn assert_has_event(event: Event) {
+    assert!(System::events().iter().any(|record| record.event == event));
+}
+
+fn handle_create_signed_puzzle(
+    account_id: u64,
+    puzzle_hash: &str,
+    answer: &str,
+    answer_nonce: &str,
+    ticket: BalanceOf<Test>,
+    duration: DurationBn,
+) {
+    // the creator signs `answer + nonce`.
+    let mut message = toVec(answer);
+    message.extend_from_slice(answer_nonce.as_bytes());
+    let answer_signed = TestSignature(account_id, message).encode();
+
+    assert_ok!(AtochaModule::create_puzzle(
+        Origin::signed(account_id),
+        toVec(puzzle_hash),
+        answer_signed,
+        toVec(answer_nonce),
+        ticket,
+        duration,
+        None,
+    ));
+}
+
+#[test]
+fn test_refund_puzzle_not_revealed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_HASH_1"),
+            500,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_HASH_2"),
+            500,
+        ));
+
+        // the creator still has time to reveal.
+        System::set_block_number(60);
+        assert_noop!(
+            AtochaModule::refund_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::RevealPeriodNotExpired
+        );
+
+        // deadline 55 + grace period 10.
+        System::set_block_number(65);
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER"),
+                toVec("NONCE"),
+            ),
+            Error::<Test>::RevealPeriodExpired
+        );
+        assert_ok!(AtochaModule::refund_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+        ));
+
+        // half of the creator ticket is split among the answerers.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            1_000_000 - 50
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 + 25
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_2),
+            1_000_000 + 25
+        );
+        assert_eq!(Balances::free_balance(AtochaModule::account_id()), 1);
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 0);
+        assert_eq!(AtochaModule::puzzle_payouts(toVec("PUZZLE_HASH")), 1100);
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_CLOSED
+        );
+        for answer_hash in vec!["ANSWER_HASH_1", "ANSWER_HASH_2"] {
+            assert_eq!(
+                AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), toVec(answer_hash))
+                    .unwrap()
+                    .answer_status,
+                PUZZLE_ANSWER_STATUS_IS_EXPIRED
+            );
+        }
+        assert_has_event(
+            AtochaEvent::TicketRefunded(CONST_ORIGIN_IS_ANSWER_1, toVec("PUZZLE_HASH"), 525).into(),
+        );
+        assert_has_event(AtochaEvent::PuzzleRefunded(toVec("PUZZLE_HASH"), 65).into());
+
+        // a puzzle can only be refunded once.
+        assert_noop!(
+            AtochaModule::refund_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::PuzzleAlreadyRevealed
+        );
+    });
+}
+
+#[test]
+fn test_revoke_puzzle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_HASH_1"),
+            500,
+        ));
+
+        // only the creator can revoke.
+        assert_noop!(
+            AtochaModule::revoke_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::NotPuzzleOwner
+        );
+
+        // create block number 5 + cutoff 20.
+        System::set_block_number(25);
+        assert_noop!(
+            AtochaModule::revoke_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::RevokeCutoffPassed
+        );
+
+        System::set_block_number(24);
+        assert_ok!(AtochaModule::revoke_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+        ));
+
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_CREATOR), 1_000_000);
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1), 1_000_000);
+        assert_eq!(Balances::free_balance(AtochaModule::account_id()), 1);
+        assert_eq!(AtochaModule::puzzle_info(toVec("PUZZLE_HASH")), None);
+        assert_eq!(
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), toVec("ANSWER_HASH_1")),
+            None
+        );
+        assert_eq!(AtochaModule::puzzle_answer_count(toVec("PUZZLE_HASH")), 0);
+        assert_eq!(
+            AtochaModule::answers_by_account(
+                CONST_ORIGIN_IS_ANSWER_1,
+                (toVec("PUZZLE_HASH"), toVec("ANSWER_HASH_1"))
+            ),
+            None
+        );
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 0);
+        assert_eq!(AtochaModule::puzzle_deadlines(55).len(), 0);
+        assert_eq!(
+            AtochaModule::puzzles_by_creator(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH")),
+            None
+        );
+        assert_has_event(
+            AtochaEvent::PuzzleRevoked(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH")).into(),
+        );
+    });
+}
+
+#[test]
+fn test_revoke_puzzle_without_answers_after_cutoff() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+
+        // nobody answered, so the cutoff does not apply.
+        System::set_block_number(40);
+        assert_ok!(AtochaModule::revoke_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+        ));
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_CREATOR), 1_000_000);
+        assert_eq!(AtochaModule::puzzle_info(toVec("PUZZLE_HASH")), None);
+
+        assert_noop!(
+            AtochaModule::revoke_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::PuzzleNotExist
+        );
+    });
+}
+
+type LegacyPuzzleInfo = (
+    u64,
+    PuzzleAnswerOption,
+    PuzzleAnswerSigned,
+    PuzzleAnswerNonce,
+    u64,
+    PuzzleStatus,
+    CreateBn,
+    DurationBn,
+    RevealBn,
+    PuzzleVersion,
+);
+type LegacyAnswer = (u64, PuzzleAnswerHash, u64, PuzzleAnswerStatus, CreateBn);
+
+#[test]
+fn test_migrate_tuples_to_answer_map() {
+    new_test_ext().execute_with(|| {
+        // write the V1 tuples the way the old runtime stored them.
+        let legacy_info: LegacyPuzzleInfo = (
+            CONST_ORIGIN_IS_CREATOR,
+            None,
+            toVec("ANSWER_SIGNED"),
+            toVec("NONCE"),
+            10,
+            PUZZLE_STATUS_IS_SOLVING,
+            5,
+            55,
+            0,
+            1,
+        );
+        let legacy_answers: Vec<LegacyAnswer> = vec![(
+            CONST_ORIGIN_IS_ANSWER_1,
+            toVec("ANSWER_HASH"),
+            500,
+            PUZZLE_ANSWER_STATUS_IS_PENDING,
+            6,
+        )];
+        unhashed::put(
+            &PuzzleInfo::<Test>::hashed_key_for(toVec("PUZZLE_HASH")),
+            &legacy_info,
+        );
+        unhashed::put(
+            &PuzzleDirectAnswer::<Test>::hashed_key_for(toVec("PUZZLE_HASH")),
+            &legacy_answers,
+        );
+        // a new chain starts on the latest layout, the old runtime reported `V1`.
+        assert_eq!(AtochaModule::storage_version(), Releases::V9);
+        StorageVersion::<Test>::put(Releases::V1);
+        // the old runtime did not fund the pot.
+        Balances::make_free_balance_be(&AtochaModule::account_id(), 0);
+
+        AtochaModule::on_runtime_upgrade();
+
+        assert_eq!(AtochaModule::storage_version(), Releases::V9);
+        assert_eq!(Balances::free_balance(AtochaModule::account_id()), 1);
+        let puzzle_content = AtochaModule::puzzle_info(toVec("PUZZLE_HASH")).unwrap();
+        assert_eq!(puzzle_content.account, CONST_ORIGIN_IS_CREATOR);
+        // the legacy tickets were never paid into the pot, so nothing can be paid out for them.
+        assert_eq!(puzzle_content.puzzle_ticket, 0);
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 0);
+        assert_eq!(puzzle_content.duration_bn, 55);
+        assert_eq!(PuzzleDirectAnswer::<Test>::get(toVec("PUZZLE_HASH")), None);
+        assert_eq!(
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), toVec("ANSWER_HASH")),
+            Some(AnswerData {
+                account: CONST_ORIGIN_IS_ANSWER_1,
+                answer_hash: toVec("ANSWER_HASH").try_into().unwrap(),
+                ticket: 0,
+                answer_status: PUZZLE_ANSWER_STATUS_IS_PENDING,
+                create_bn: 6,
+                answer_index: 0,
+            })
+        );
+        assert_eq!(AtochaModule::puzzle_answer_count(toVec("PUZZLE_HASH")), 1);
+        assert!(AtochaModule::answers_by_account(
+            CONST_ORIGIN_IS_ANSWER_1,
+            (toVec("PUZZLE_HASH"), toVec("ANSWER_HASH"))
+        )
+        .is_some());
+        assert!(
+            AtochaModule::puzzles_by_creator(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH"))
+                .is_some()
+        );
+        assert!(
+            AtochaModule::puzzles_by_status(PUZZLE_STATUS_IS_SOLVING, toVec("PUZZLE_HASH"))
+                .is_some()
+        );
+        // a solving puzzle is not scheduled for pruning.
+        assert_eq!(AtochaModule::puzzle_closed_at(toVec("PUZZLE_HASH")), None);
+
+        // refunding the legacy puzzle takes nothing from the pot.
+        System::set_block_number(AtochaModule::reveal_deadline(55));
+        assert_ok!(AtochaModule::refund_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+        ));
+        assert_eq!(Balances::free_balance(AtochaModule::account_id()), 1);
+        assert_eq!(
+            AtochaModule::unpaid_payouts(CONST_ORIGIN_IS_ANSWER_1, (toVec("PUZZLE_HASH"), None)),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_migrate_tuples_out_of_bounds() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(60);
+        let legacy_info = |answer_signed: Vec<u8>, duration_bn: DurationBn| -> LegacyPuzzleInfo {
+            (
+                CONST_ORIGIN_IS_CREATOR,
+                None,
+                answer_signed,
+                toVec("NONCE"),
+                10,
+                PUZZLE_STATUS_IS_SOLVING,
+                5,
+                duration_bn,
+                0,
+                1,
+            )
+        };
+        let legacy_answer = |account: u64, answer_hash: Vec<u8>| -> LegacyAnswer {
+            (
+                account,
+                answer_hash,
+                500,
+                PUZZLE_ANSWER_STATUS_IS_PENDING,
+                6,
+            )
+        };
+        let put_legacy = |puzzle_hash: &str, info: LegacyPuzzleInfo, answers: Vec<LegacyAnswer>| {
+            unhashed::put(
+                &PuzzleInfo::<Test>::hashed_key_for(toVec(puzzle_hash)),
+                &info,
+            );
+            unhashed::put(
+                &PuzzleDirectAnswer::<Test>::hashed_key_for(toVec(puzzle_hash)),
+                &answers,
+            );
+        };
+        // the signature is longer than `MaxSignatureLength`.
+        put_legacy(
+            "LONG_PUZZLE_HASH",
+            legacy_info(vec![1; 129], 100),
+            vec![legacy_answer(
+                CONST_ORIGIN_IS_ANSWER_1,
+                toVec("ANSWER_HASH"),
+            )],
+        );
+        // five answers, one with a hash longer than `MaxHashLength`.
+        put_legacy(
+            "OPEN_PUZZLE_HASH",
+            legacy_info(toVec("ANSWER_SIGNED"), 100),
+            vec![
+                legacy_answer(CONST_ORIGIN_IS_ANSWER_1, toVec("ANSWER_HASH_1")),
+                legacy_answer(CONST_ORIGIN_IS_ANSWER_2, vec![1; 65]),
+                legacy_answer(CONST_ORIGIN_IS_ANSWER_2, toVec("ANSWER_HASH_2")),
+                legacy_answer(CONST_ORIGIN_IS_ANSWER_3, toVec("ANSWER_HASH_3")),
+                legacy_answer(CONST_ORIGIN_IS_ANSWER_3, toVec("ANSWER_HASH_4")),
+            ],
+        );
+        // the deadline passed before the upgrade.
+        put_legacy(
+            "LATE_PUZZLE_HASH",
+            legacy_info(toVec("ANSWER_SIGNED"), 55),
+            Vec::new(),
+        );
+        StorageVersion::<Test>::put(Releases::V1);
+
+        AtochaModule::on_runtime_upgrade();
+
+        // a puzzle which does not fit the bounds is removed with its answers.
+        assert_eq!(AtochaModule::puzzle_info(toVec("LONG_PUZZLE_HASH")), None);
+        assert_eq!(
+            AtochaModule::puzzle_answer_count(toVec("LONG_PUZZLE_HASH")),
+            0
+        );
+        assert_eq!(
+            AtochaModule::puzzle_answers(toVec("LONG_PUZZLE_HASH"), toVec("ANSWER_HASH")),
+            None
+        );
+        assert_eq!(
+            AtochaModule::answers_by_account(
+                CONST_ORIGIN_IS_ANSWER_1,
+                (toVec("LONG_PUZZLE_HASH"), toVec("ANSWER_HASH"))
+            ),
+            None
+        );
+
+        // the first answers which fit the bounds are kept.
+        assert_eq!(
+            AtochaModule::puzzle_answer_count(toVec("OPEN_PUZZLE_HASH")),
+            3
+        );
+        for answer_hash in vec!["ANSWER_HASH_1", "ANSWER_HASH_2", "ANSWER_HASH_3"] {
+            assert!(
+                AtochaModule::puzzle_answers(toVec("OPEN_PUZZLE_HASH"), toVec(answer_hash))
+                    .is_some()
+            );
+        }
+        assert_eq!(
+            AtochaModule::puzzle_answers(toVec("OPEN_PUZZLE_HASH"), toVec("ANSWER_HASH_4")),
+            None
+        );
+        assert_eq!(
+            AtochaModule::answers_in_order(&toVec("OPEN_PUZZLE_HASH"))
+                .into_iter()
+                .map(|(answer_hash, answer_content)| (answer_hash, answer_content.answer_index))
+                .collect::<Vec<_>>(),
+            vec![
+                (toVec("ANSWER_HASH_1"), 0),
+                (toVec("ANSWER_HASH_2"), 1),
+                (toVec("ANSWER_HASH_3"), 2),
+            ]
+        );
+
+        // a solving puzzle is scheduled, or up to time when its deadline has passed.
+        assert_eq!(
+            AtochaModule::puzzle_deadlines(100).into_inner(),
+            vec![toVec("OPEN_PUZZLE_HASH")]
+        );
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("LATE_PUZZLE_HASH"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_UP_TO_TIME
+        );
+        assert!(AtochaModule::puzzles_by_status(
+            PUZZLE_STATUS_IS_UP_TO_TIME,
+            toVec("LATE_PUZZLE_HASH")
+        )
+        .is_some());
+
+        System::set_block_number(100);
+        AtochaModule::on_initialize(100);
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("OPEN_PUZZLE_HASH"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_UP_TO_TIME
+        );
+        assert!(AtochaModule::puzzles_by_status(
+            PUZZLE_STATUS_IS_SOLVING,
+            toVec("OPEN_PUZZLE_HASH")
+        )
+        .is_none());
+    });
+}
+
+#[test]
+fn test_migrate_answer_index() {
+    new_test_ext().execute_with(|| {
+        // write the answers the way the `V7` runtime stored them.
+        for (answer_hash, create_bn) in vec![
+            ("ANSWER_HASH_1", 7),
+            ("ANSWER_HASH_2", 6),
+            ("ANSWER_HASH_3", 6),
+        ] {
+            let legacy_answer: LegacyAnswer = (
+                CONST_ORIGIN_IS_ANSWER_1,
+                toVec(answer_hash),
+                500,
+                PUZZLE_ANSWER_STATUS_IS_PENDING,
+                create_bn,
+            );
+            unhashed::put(
+                &PuzzleAnswers::<Test>::hashed_key_for(toVec("PUZZLE_HASH"), toVec(answer_hash)),
+                &legacy_answer,
+            );
+        }
+        StorageVersion::<Test>::put(Releases::V7);
+
+        AtochaModule::on_runtime_upgrade();
+
+        // the answers are numbered by block number.
+        assert_eq!(AtochaModule::storage_version(), Releases::V9);
+        let answer_index = |answer_hash: &str| {
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), toVec(answer_hash))
+                .unwrap()
+                .answer_index
+        };
+        assert_eq!(answer_index("ANSWER_HASH_1"), 2);
+        assert_eq!(
+            answer_index("ANSWER_HASH_2") + answer_index("ANSWER_HASH_3"),
+            1
+        );
+        let answer_content =
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), toVec("ANSWER_HASH_1")).unwrap();
+        assert_eq!(answer_content.ticket, 500);
+        assert_eq!(answer_content.create_bn, 7);
+    });
+}
+
+#[test]
+fn test_answers_in_submission_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+
+        // answers of the same block keep the order they were submitted in.
+        let submitted = vec![
+            (CONST_ORIGIN_IS_ANSWER_1, "ANSWER_HASH_C"),
+            (CONST_ORIGIN_IS_ANSWER_2, "ANSWER_HASH_A"),
+            (CONST_ORIGIN_IS_ANSWER_3, "ANSWER_HASH_B"),
+        ];
+        for (answerer, answer_hash) in submitted.iter() {
+            assert_ok!(AtochaModule::answer_puzzle(
+                Origin::signed(*answerer),
+                toVec("PUZZLE_HASH"),
+                toVec(answer_hash),
+                500,
+            ));
+        }
+        assert_eq!(
+            AtochaModule::answers_in_order(&toVec("PUZZLE_HASH"))
+                .into_iter()
+                .map(|(answer_hash, answer_content)| (
+                    answer_content.account,
+                    answer_hash,
+                    answer_content.answer_index
+                ))
+                .collect::<Vec<_>>(),
+            submitted
+                .into_iter()
+                .enumerate()
+                .map(|(answer_index, (answerer, answer_hash))| (
+                    answerer,
+                    toVec(answer_hash),
+                    answer_index as u32
+                ))
+                .collect::<Vec<_>>()
+        );
+    });
+}
+
+#[test]
+fn test_migrate_unpaid_payouts_by_asset() {
+    new_test_ext().execute_with(|| {
+        // write the payout under the `(account, puzzle_hash)` key of the `V8` runtime.
+        let mut key = UnpaidPayouts::<Test>::final_prefix().to_vec();
+        key.extend(Blake2_128Concat::hash(&CONST_ORIGIN_IS_ANSWER_1.encode()));
+        key.extend(Blake2_128Concat::hash(&toVec("PUZZLE_HASH").encode()));
+        unhashed::put(&key, &(Some(0u32), 28u64));
+        StorageVersion::<Test>::put(Releases::V8);
+
+        AtochaModule::on_runtime_upgrade();
+
+        assert_eq!(AtochaModule::storage_version(), Releases::V9);
+        assert_eq!(unhashed::get_raw(&key), None);
+        assert_eq!(
+            AtochaModule::unpaid_payouts(CONST_ORIGIN_IS_ANSWER_1, (toVec("PUZZLE_HASH"), Some(0))),
+            Some(28)
+        );
+        assert_eq!(
+            AtochaModule::unpaid_payouts(CONST_ORIGIN_IS_ANSWER_1, (toVec("PUZZLE_HASH"), None)),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_bounded_puzzle_data() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        // the puzzle hash, signature and nonce are limited by the config.
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                vec![1; 65],
+                toVec("ANSWER_SIGNED"),
+                toVec("NONCE"),
+                10,
+                50,
+                None,
+            ),
+            Error::<Test>::HashTooLong
+        );
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                vec![1; 129],
+                toVec("NONCE"),
+                10,
+                50,
+                None,
+            ),
+            Error::<Test>::SignatureTooLong
+        );
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_SIGNED"),
+                vec![1; 65],
+                10,
+                50,
+                None,
+            ),
+            Error::<Test>::NonceTooLong
+        );
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            10,
+            50,
+        );
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                vec![1; 65],
+                500,
+            ),
+            Error::<Test>::HashTooLong
+        );
+
+        // at most 3 answers for one puzzle.
+        for (account, answer_hash) in vec![
+            (CONST_ORIGIN_IS_ANSWER_1, "ANSWER_HASH_1"),
+            (CONST_ORIGIN_IS_ANSWER_2, "ANSWER_HASH_2"),
+            (CONST_ORIGIN_IS_ANSWER_3, "ANSWER_HASH_3"),
+        ] {
+            assert_ok!(AtochaModule::answer_puzzle(
+                Origin::signed(account),
+                toVec("PUZZLE_HASH"),
+                toVec(answer_hash),
+                500,
+            ));
+        }
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_HASH_4"),
+                500,
+            ),
+            Error::<Test>::TooManyAnswers
+        );
+    });
+}
+
+#[test]
+fn test_revise_puzzle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_V1",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH_V1"),
+            toVec("ANSWER_HASH"),
+            500,
+        ));
+
+        // only the creator can revise the puzzle.
+        assert_noop!(
+            AtochaModule::revise_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH_V1"),
+                toVec("PUZZLE_HASH_V2"),
+                toVec("SIGNED"),
+                toVec("NONCE"),
+                100,
+                50,
+            ),
+            Error::<Test>::NotPuzzleOwner
+        );
+
+        System::set_block_number(8);
+        assert_ok!(AtochaModule::revise_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH_V1"),
+            toVec("PUZZLE_HASH_V2"),
+            toVec("SIGNED"),
+            toVec("NONCE"),
+            200,
+            50,
+        ));
+        assert_has_event(
+            AtochaEvent::PuzzleRevised(
+                CONST_ORIGIN_IS_CREATOR,
+                toVec("PUZZLE_HASH_V1"),
+                toVec("PUZZLE_HASH_V2"),
+                2,
+            )
+            .into(),
+        );
+        let puzzle_content = AtochaModule::puzzle_info(toVec("PUZZLE_HASH_V2")).unwrap();
+        assert_eq!(puzzle_content.puzzle_version, 2);
+        assert_eq!(puzzle_content.duration_bn, 58);
+        assert_eq!(
+            AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH_V2")),
+            200
+        );
+        assert_eq!(
+            AtochaModule::puzzle_revised_to(toVec("PUZZLE_HASH_V1")),
+            Some(toVec("PUZZLE_HASH_V2"))
+        );
+        assert_eq!(
+            AtochaModule::puzzle_version_history(&toVec("PUZZLE_HASH_V2")),
+            vec![toVec("PUZZLE_HASH_V1"), toVec("PUZZLE_HASH_V2")]
+        );
+
+        // the answer stays with the first version, which takes no more answers.
+        assert!(
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH_V1"), toVec("ANSWER_HASH")).is_some()
+        );
+        assert_eq!(
+            AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH_V1")),
+            600
+        );
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+                toVec("PUZZLE_HASH_V1"),
+                toVec("OTHER_ANSWER_HASH"),
+                500,
+            ),
+            Error::<Test>::PuzzleAlreadyRevised
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH_V2"),
+            toVec("OTHER_ANSWER_HASH"),
+            500,
+        ));
+
+        // a version is revised once, the next revision starts from the latest version.
+        assert_noop!(
+            AtochaModule::revise_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH_V1"),
+                toVec("PUZZLE_HASH_V3"),
+                toVec("SIGNED"),
+                toVec("NONCE"),
+                100,
+                50,
+            ),
+            Error::<Test>::PuzzleAlreadyRevised
+        );
+
+        // the version links are removed with the reaped versions.
+        assert_ok!(AtochaModule::force_close_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH_V1"),
+        ));
+        assert_ok!(AtochaModule::force_close_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH_V2"),
+        ));
+        System::set_block_number(200);
+        assert_ok!(AtochaModule::reap_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH_V1"),
+        ));
+        assert_ok!(AtochaModule::reap_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH_V2"),
+        ));
+        assert_eq!(
+            AtochaModule::puzzle_revised_to(toVec("PUZZLE_HASH_V1")),
+            None
+        );
+        assert_eq!(
+            AtochaModule::puzzle_revised_from(toVec("PUZZLE_HASH_V2")),
+            None
+        );
+    });
+}
+
+#[test]
+fn test_commit_and_reveal_answer() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        let commitment_1 = AtochaModule::make_answer_commitment(
+            &toVec("ANSWER_TEXT"),
+            &toVec("SALT_1"),
+            &CONST_ORIGIN_IS_ANSWER_1,
+        );
+        let commitment_2 = AtochaModule::make_answer_commitment(
+            &toVec("WRONG_TEXT"),
+            &toVec("SALT_2"),
+            &CONST_ORIGIN_IS_ANSWER_2,
+        );
+        let commitment_3 = AtochaModule::make_answer_commitment(
+            &toVec("ANSWER_TEXT"),
+            &toVec("SALT_3"),
+            &CONST_ORIGIN_IS_ANSWER_3,
+        );
+        assert_ok!(AtochaModule::commit_answer(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            commitment_1.clone(),
+            500,
+        ));
+        assert_has_event(
+            AtochaEvent::AnswerCommitted(
+                CONST_ORIGIN_IS_ANSWER_1,
+                commitment_1.clone(),
+                toVec("PUZZLE_HASH"),
+                5,
+            )
+            .into(),
+        );
+        assert_ok!(AtochaModule::commit_answer(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            commitment_2.clone(),
+            400,
+        ));
+        assert_ok!(AtochaModule::commit_answer(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+            commitment_3.clone(),
+            300,
+        ));
+
+        // the answers can not be revealed before the puzzle.
+        assert_noop!(
+            AtochaModule::reveal_answer(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("SALT_1"),
+            ),
+            Error::<Test>::AnswerRevealNotOpen
+        );
+
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        let puzzle_content = AtochaModule::puzzle_info(toVec("PUZZLE_HASH")).unwrap();
+        assert_eq!(
+            puzzle_content.puzzle_status,
+            PUZZLE_STATUS_IS_ANSWER_REVEALING
+        );
+        // nothing is paid until the puzzle is settled.
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 1300);
+
+        // a commitment is bound to the account which made it.
+        assert_noop!(
+            AtochaModule::reveal_answer(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("SALT_1"),
+            ),
+            Error::<Test>::CommitmentNotExist
+        );
+        assert_ok!(AtochaModule::reveal_answer(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("SALT_1"),
+        ));
+        assert_has_event(
+            AtochaEvent::AnswerSolved(
+                CONST_ORIGIN_IS_ANSWER_1,
+                toVec("PUZZLE_HASH"),
+                commitment_1.clone(),
+            )
+            .into(),
+        );
+        assert_noop!(
+            AtochaModule::reveal_answer(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("SALT_1"),
+            ),
+            Error::<Test>::AnswerAlreadyRevealed
+        );
+        assert_ok!(AtochaModule::reveal_answer(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            toVec("WRONG_TEXT"),
+            toVec("SALT_2"),
+        ));
+        assert_eq!(
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), commitment_2)
+                .unwrap()
+                .answer_status,
+            PUZZLE_ANSWER_STATUS_IS_WRONG
+        );
+
+        // the answer reveal window is still open.
+        assert_noop!(
+            AtochaModule::settle_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::AnswerRevealPeriodNotExpired
+        );
+
+        System::set_block_number(25);
+        assert_noop!(
+            AtochaModule::reveal_answer(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_TEXT"),
+                toVec("SALT_3"),
+            ),
+            Error::<Test>::AnswerRevealPeriodExpired
+        );
+        assert_ok!(AtochaModule::settle_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+        ));
+
+        // the commitment which was never revealed is wrong.
+        assert_eq!(
+            AtochaModule::puzzle_answers(toVec("PUZZLE_HASH"), commitment_3)
+                .unwrap()
+                .answer_status,
+            PUZZLE_ANSWER_STATUS_IS_WRONG
+        );
+        let puzzle_content = AtochaModule::puzzle_info(toVec("PUZZLE_HASH")).unwrap();
+        assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+
+        // 10% creator fee, the rest goes to the only winner.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            1_000_000 - 100 + 130
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 500 + 1170
+        );
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 0);
+        assert_has_event(AtochaEvent::PuzzleSettled(toVec("PUZZLE_HASH"), 25).into());
+    });
+}
+
+#[test]
+fn test_puzzle_details_queries() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_1",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        System::set_block_number(6);
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_ANSWER_1,
+            "PUZZLE_HASH_3",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        for (answer_account, answer_hash) in vec![
+            (CONST_ORIGIN_IS_ANSWER_2, "ANSWER_HASH_1"),
+            (CONST_ORIGIN_IS_ANSWER_3, "ANSWER_HASH_2"),
+        ] {
+            assert_ok!(AtochaModule::answer_puzzle(
+                Origin::signed(answer_account),
+                toVec("PUZZLE_HASH_1"),
+                toVec(answer_hash),
+                500,
+            ));
+        }
+
+        let puzzle_details = AtochaModule::puzzle_details(&toVec("PUZZLE_HASH_1")).unwrap();
+        assert_eq!(puzzle_details.account, CONST_ORIGIN_IS_CREATOR);
+        assert_eq!(puzzle_details.answer_nonce, toVec("NONCE"));
+        assert_eq!(puzzle_details.prize_pool, 1100);
+        assert_eq!(puzzle_details.answer_count, 2);
+        assert!(AtochaModule::puzzle_details(&toVec("PUZZLE_HASH_4")).is_none());
+
+        let answer_list = AtochaModule::answer_details_page(&toVec("PUZZLE_HASH_1"), 1, 10);
+        assert_eq!(answer_list.len(), 1);
+        assert_eq!(answer_list[0].account, CONST_ORIGIN_IS_ANSWER_3);
+
+        let puzzle_list = AtochaModule::puzzle_details_by_creator(&CONST_ORIGIN_IS_CREATOR, 0, 10);
+        let puzzle_hash_list: Vec<Vec<u8>> = puzzle_list
+            .into_iter()
+            .map(|puzzle_details| puzzle_details.puzzle_hash)
+            .collect();
+        assert_eq!(
+            puzzle_hash_list,
+            vec![toVec("PUZZLE_HASH_1"), toVec("PUZZLE_HASH_2")]
+        );
+        assert_eq!(
+            AtochaModule::puzzle_details_by_creator(&CONST_ORIGIN_IS_CREATOR, 0, 1).len(),
+            1
+        );
+        assert_eq!(
+            AtochaModule::puzzle_details_by_status(PUZZLE_STATUS_IS_SOLVING, 1, 10).len(),
+            2
+        );
+        assert!(AtochaModule::puzzle_details_by_status(PUZZLE_STATUS_IS_SOLVED, 0, 10).is_empty());
+
+        // a puzzle moves to the index of its new status.
+        assert_ok!(AtochaModule::force_close_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH_2"),
+        ));
+        let puzzle_list = AtochaModule::puzzle_details_by_status(PUZZLE_STATUS_IS_CLOSED, 0, 10);
+        assert_eq!(puzzle_list.len(), 1);
+        assert_eq!(puzzle_list[0].puzzle_hash, toVec("PUZZLE_HASH_2"));
+        assert_eq!(
+            AtochaModule::puzzle_details_by_status(PUZZLE_STATUS_IS_SOLVING, 0, 10).len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn test_points_and_leaderboard() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_1",
+            "ANSWER_TEXT_1",
+            "NONCE",
+            100,
+            50,
+        );
+        System::set_block_number(6);
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH_1"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT_1")),
+            500,
+        ));
+        System::set_block_number(8);
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH_1"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT_1")),
+            500,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH_1"),
+            AtochaModule::make_answer_hash(&toVec("WRONG_TEXT")),
+            500,
+        ));
+
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH_1"),
+            toVec("ANSWER_TEXT_1"),
+            toVec("NONCE"),
+        ));
+
+        // the points fall with the blocks taken to solve, the wrong answer gets nothing.
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_ANSWER_1),
+            PlayerScore {
+                points: 99,
+                solved_count: 1,
+                total_solve_blocks: 1,
+                created_solved_count: 0,
+            }
+        );
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_ANSWER_2).points,
+            97
+        );
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_ANSWER_3),
+            PlayerScore::default()
+        );
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_CREATOR).points,
+            20
+        );
+        assert_has_event(
+            AtochaEvent::PointsAwarded(CONST_ORIGIN_IS_ANSWER_1, toVec("PUZZLE_HASH_1"), 99).into(),
+        );
+        assert_eq!(
+            AtochaModule::leaderboard(),
+            vec![
+                (CONST_ORIGIN_IS_ANSWER_1, 99),
+                (CONST_ORIGIN_IS_ANSWER_2, 97)
+            ]
+        );
+
+        System::set_block_number(20);
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "ANSWER_TEXT_2",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH_2"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT_2")),
+            500,
+        ));
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH_2"),
+            toVec("ANSWER_TEXT_2"),
+            toVec("NONCE"),
+        ));
+
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_ANSWER_2),
+            PlayerScore {
+                points: 197,
+                solved_count: 2,
+                total_solve_blocks: 3,
+                created_solved_count: 0,
+            }
+        );
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_CREATOR),
+            PlayerScore {
+                points: 30,
+                solved_count: 0,
+                total_solve_blocks: 0,
+                created_solved_count: 2,
+            }
+        );
+        // the board keeps the `LeaderboardSize` best accounts.
+        assert_eq!(
+            AtochaModule::leaderboard(),
+            vec![
+                (CONST_ORIGIN_IS_ANSWER_2, 197),
+                (CONST_ORIGIN_IS_ANSWER_1, 99)
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_season_rollover() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // only the season origin schedules seasons, and they must not overlap.
+        assert_noop!(
+            AtochaModule::schedule_season(Origin::signed(CONST_ORIGIN_IS_CREATOR), 10, 30, 1000),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AtochaModule::schedule_season(Origin::root(), 30, 30, 1000),
+            Error::<Test>::InvalidSeasonPeriod
+        );
+        assert_ok!(AtochaModule::schedule_season(Origin::root(), 10, 30, 1000));
+        assert_has_event(AtochaEvent::SeasonScheduled(1, 10, 30, 1000).into());
+        assert_noop!(
+            AtochaModule::schedule_season(Origin::root(), 20, 40, 0),
+            Error::<Test>::SeasonOverlaps
+        );
+        assert_ok!(AtochaModule::schedule_season(Origin::root(), 30, 50, 0));
+
+        System::set_block_number(10);
+        AtochaModule::on_initialize(10);
+        assert_eq!(AtochaModule::active_season(), Some(1));
+        assert_has_event(AtochaEvent::SeasonStarted(1, 10).into());
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_eq!(AtochaModule::puzzle_season(toVec("PUZZLE_HASH")), Some(1));
+        System::set_block_number(11);
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        System::set_block_number(12);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        assert_eq!(
+            AtochaModule::season_scores(1, CONST_ORIGIN_IS_ANSWER_1).points,
+            99
+        );
+        assert_eq!(
+            AtochaModule::season_leaderboard(1),
+            vec![
+                (CONST_ORIGIN_IS_ANSWER_1, 99),
+                (CONST_ORIGIN_IS_CREATOR, 10)
+            ]
+        );
+
+        // the treasury keeps its existential deposit, the rest pays the bonus.
+        let treasury = AtochaModule::season_treasury();
+        Balances::make_free_balance_be(&treasury, 601);
+        let answer_balance = Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1);
+        let creator_balance = Balances::free_balance(CONST_ORIGIN_IS_CREATOR);
+
+        System::set_block_number(30);
+        AtochaModule::on_initialize(30);
+        assert_has_event(AtochaEvent::SeasonEnded(1, 30).into());
+        assert_has_event(AtochaEvent::SeasonStarted(2, 30).into());
+        assert_eq!(AtochaModule::active_season(), Some(2));
+        assert_eq!(Balances::free_balance(&treasury), 1);
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            answer_balance + 300
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            creator_balance + 300
+        );
+        assert_has_event(AtochaEvent::SeasonBonusPaid(1, CONST_ORIGIN_IS_ANSWER_1, 300).into());
+
+        // the final leaderboard of the season is kept.
+        assert_eq!(AtochaModule::season_leaderboard(1).len(), 2);
+        assert!(AtochaModule::season_leaderboard(2).is_empty());
+
+        // a puzzle counts for the season it was created in, even when solved after it.
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        System::set_block_number(31);
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH_2"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+
+        System::set_block_number(50);
+        AtochaModule::on_initialize(50);
+        assert_eq!(AtochaModule::active_season(), None);
+        System::set_block_number(51);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH_2"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        assert_eq!(
+            AtochaModule::season_scores(2, CONST_ORIGIN_IS_ANSWER_2).points,
+            99
+        );
+    });
+}
+
+#[test]
+fn test_admin_moderation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        // a blacklisted account can not create puzzles.
+        assert_noop!(
+            AtochaModule::set_blacklisted(Origin::signed(CONST_ORIGIN_IS_CREATOR), 4, true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(AtochaModule::set_blacklisted(
+            Origin::root(),
+            CONST_ORIGIN_IS_ANSWER_3,
+            true
+        ));
+        assert_has_event(AtochaEvent::AccountBlacklisted(CONST_ORIGIN_IS_ANSWER_3, true).into());
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH_3"),
+                toVec("SIGNED"),
+                toVec("NONCE"),
+                100,
+                50,
+                None,
+            ),
+            Error::<Test>::CreatorBlacklisted
+        );
+
+        // a paused pallet rejects every signed call.
+        assert_ok!(AtochaModule::set_paused(Origin::root(), true));
+        assert_has_event(AtochaEvent::PalletPaused(true).into());
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH_1"),
+                toVec("SIGNED"),
+                toVec("NONCE"),
+                100,
+                50,
+                None,
+            ),
+            Error::<Test>::PalletPaused
+        );
+        assert_ok!(AtochaModule::set_paused(Origin::root(), false));
+
+        // a closed puzzle refunds every ticket.
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_1",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH_1"),
+            toVec("ANSWER_HASH"),
+            500,
+        ));
+        assert_ok!(AtochaModule::force_close_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH_1")
+        ));
+        assert_has_event(AtochaEvent::PuzzleForceClosed(toVec("PUZZLE_HASH_1")).into());
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_CREATOR), 1_000_000);
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1), 1_000_000);
+        let puzzle_content = AtochaModule::puzzle_info(toVec("PUZZLE_HASH_1")).unwrap();
+        assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_CLOSED);
+        assert_noop!(
+            AtochaModule::force_close_puzzle(Origin::root(), toVec("PUZZLE_HASH_1")),
+            Error::<Test>::PuzzleAlreadyRevealed
+        );
+        // the creator can not reveal a closed puzzle before its reveal deadline.
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH_1"),
+                toVec("ANSWER"),
+                toVec("NONCE"),
+            ),
+            Error::<Test>::PuzzleAlreadyRevealed
+        );
+        assert_eq!(
+            AtochaModule::puzzle_closed_at(toVec("PUZZLE_HASH_1")),
+            Some(5)
+        );
+        assert_ok!(AtochaModule::create_slot_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("SLOT_PUZZLE_HASH"),
+            vec![toVec("SIGNED")],
+            toVec("NONCE"),
+            100,
+            50,
+        ));
+        assert_ok!(AtochaModule::force_close_puzzle(
+            Origin::root(),
+            toVec("SLOT_PUZZLE_HASH")
+        ));
+        assert_noop!(
+            AtochaModule::reveal_puzzle_slots(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("SLOT_PUZZLE_HASH"),
+                vec![toVec("ANSWER")],
+                toVec("NONCE"),
+            ),
+            Error::<Test>::PuzzleAlreadyRevealed
+        );
+
+        // the admin reveals without the creator signature, and the pool is paid.
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH_2"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        assert_ok!(AtochaModule::force_reveal_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH_2"),
+            toVec("ANSWER_TEXT"),
+        ));
+        assert_has_event(
+            AtochaEvent::PuzzleForceRevealed(toVec("PUZZLE_HASH_2"), toVec("ANSWER_TEXT")).into(),
+        );
+        let puzzle_content = AtochaModule::puzzle_info(toVec("PUZZLE_HASH_2")).unwrap();
+        assert_eq!(puzzle_content.puzzle_status, PUZZLE_STATUS_IS_SOLVED);
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 500 + 540
+        );
+    });
+}
+
+#[test]
+fn test_puzzle_hints() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+
+        // only the creator adds hints, and a hint must unlock or be for sale.
+        assert_noop!(
+            AtochaModule::add_hint(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_hint_commitment(b"HINT_1"),
+                Some(10),
+                None,
+            ),
+            Error::<Test>::NotPuzzleOwner
+        );
+        assert_noop!(
+            AtochaModule::add_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_hint_commitment(b"HINT_1"),
+                None,
+                None,
+            ),
+            Error::<Test>::HintNeverAvailable
+        );
+        assert_ok!(AtochaModule::add_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_hint_commitment(b"HINT_1"),
+            Some(10),
+            Some(20),
+        ));
+        assert_has_event(
+            AtochaEvent::HintAdded(CONST_ORIGIN_IS_CREATOR, toVec("PUZZLE_HASH"), 0).into(),
+        );
+        assert_ok!(AtochaModule::add_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_hint_commitment(b"HINT_2"),
+            None,
+            Some(30),
+        ));
+        assert_noop!(
+            AtochaModule::add_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_hint_commitment(b"HINT_3"),
+                Some(20),
+                None,
+            ),
+            Error::<Test>::TooManyHints
+        );
+        assert_eq!(
+            AtochaModule::puzzle_hints(toVec("PUZZLE_HASH"), 0),
+            Some(HintData {
+                commitment: AtochaModule::make_hint_commitment(b"HINT_1"),
+                content: None,
+                unlock_bn: Some(15),
+                price: Some(20),
+                unlocked: false,
+                purchase_count: 0,
+            })
+        );
+        assert_eq!(
+            AtochaModule::hint_unlocks(15).into_inner(),
+            vec![(toVec("PUZZLE_HASH"), 0)]
+        );
+
+        // a bought hint adds the price to the prize pool, and the creator delivers it.
+        assert_ok!(AtochaModule::buy_hint(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            0,
+        ));
+        assert_has_event(
+            AtochaEvent::HintPurchased(CONST_ORIGIN_IS_ANSWER_1, toVec("PUZZLE_HASH"), 0, 20)
+                .into(),
+        );
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 120);
+        assert!(!AtochaModule::hint_available(
+            &toVec("PUZZLE_HASH"),
+            0,
+            &CONST_ORIGIN_IS_ANSWER_1
+        ));
+        assert_noop!(
+            AtochaModule::deliver_hint(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                0,
+                CONST_ORIGIN_IS_ANSWER_1,
+                toVec("SEALED_HINT_1"),
+            ),
+            Error::<Test>::NotPuzzleOwner
+        );
+        assert_noop!(
+            AtochaModule::deliver_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                0,
+                CONST_ORIGIN_IS_ANSWER_2,
+                toVec("SEALED_HINT_1"),
+            ),
+            Error::<Test>::HintNotPurchased
+        );
+        assert_ok!(AtochaModule::deliver_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            0,
+            CONST_ORIGIN_IS_ANSWER_1,
+            toVec("SEALED_HINT_1"),
+        ));
+        assert_has_event(
+            AtochaEvent::HintDelivered(CONST_ORIGIN_IS_ANSWER_1, toVec("PUZZLE_HASH"), 0).into(),
+        );
+        assert_noop!(
+            AtochaModule::deliver_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                0,
+                CONST_ORIGIN_IS_ANSWER_1,
+                toVec("SEALED_HINT_1"),
+            ),
+            Error::<Test>::HintAlreadyDelivered
+        );
+        assert!(AtochaModule::hint_available(
+            &toVec("PUZZLE_HASH"),
+            0,
+            &CONST_ORIGIN_IS_ANSWER_1
+        ));
+        assert!(!AtochaModule::hint_available(
+            &toVec("PUZZLE_HASH"),
+            0,
+            &CONST_ORIGIN_IS_ANSWER_2
+        ));
+        assert_noop!(
+            AtochaModule::buy_hint(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                0,
+            ),
+            Error::<Test>::HintAlreadyAvailable
+        );
+        assert_noop!(
+            AtochaModule::buy_hint(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                2,
+            ),
+            Error::<Test>::HintNotExist
+        );
+        assert_noop!(
+            AtochaModule::reveal_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                0,
+                toVec("HINT_1"),
+            ),
+            Error::<Test>::HintNotUnlocked
+        );
+
+        // the creator reveals the hint from its unlock block number.
+        System::set_block_number(15);
+        AtochaModule::on_initialize(15);
+        assert_has_event(AtochaEvent::HintUnlocked(toVec("PUZZLE_HASH"), 0, 15).into());
+        assert!(!AtochaModule::hint_available(
+            &toVec("PUZZLE_HASH"),
+            0,
+            &CONST_ORIGIN_IS_ANSWER_2
+        ));
+        assert_noop!(
+            AtochaModule::reveal_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                0,
+                toVec("HINT_2"),
+            ),
+            Error::<Test>::HintCommitmentMismatch
+        );
+        assert_ok!(AtochaModule::reveal_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            0,
+            toVec("HINT_1"),
+        ));
+        assert_has_event(AtochaEvent::HintRevealed(toVec("PUZZLE_HASH"), 0).into());
+        assert_eq!(
+            AtochaModule::puzzle_hints(toVec("PUZZLE_HASH"), 0)
+                .unwrap()
+                .content,
+            Some(toVec("HINT_1").try_into().unwrap())
+        );
+        assert!(AtochaModule::hint_available(
+            &toVec("PUZZLE_HASH"),
+            0,
+            &CONST_ORIGIN_IS_ANSWER_2
+        ));
+        assert_noop!(
+            AtochaModule::reveal_hint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                0,
+                toVec("HINT_1"),
+            ),
+            Error::<Test>::HintAlreadyRevealed
+        );
+        assert_noop!(
+            AtochaModule::buy_hint(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+                toVec("PUZZLE_HASH"),
+                0,
+            ),
+            Error::<Test>::HintAlreadyAvailable
+        );
+        assert_ok!(AtochaModule::buy_hint(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            1,
+        ));
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 150);
+
+        // the hint fees are returned when the puzzle is refunded.
+        System::set_block_number(65);
+        assert_ok!(AtochaModule::refund_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+        ));
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_CREATOR), 1_000_000);
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1), 1_000_000);
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_ANSWER_2), 1_000_000);
+        assert_eq!(Balances::free_balance(AtochaModule::account_id()), 1);
+    });
+}
+
+#[test]
+fn test_refund_underfunded_pool() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        for (answerer, answer_hash) in vec![
+            (CONST_ORIGIN_IS_ANSWER_1, "ANSWER_HASH_1"),
+            (CONST_ORIGIN_IS_ANSWER_2, "ANSWER_HASH_2"),
+        ] {
+            assert_ok!(AtochaModule::answer_puzzle(
+                Origin::signed(answerer),
+                toVec("PUZZLE_HASH"),
+                toVec(answer_hash),
+                500,
+            ));
+        }
+        // the pool holds less than the stored tickets.
+        PuzzlePrizePool::<Test>::insert(toVec("PUZZLE_HASH"), 500);
+
+        System::set_block_number(65);
+        assert_ok!(AtochaModule::refund_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+        ));
+
+        // the answer tickets are refunded in proportion, nothing is left for the creator.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 250
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_2),
+            1_000_000 - 250
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            1_000_000 - 100
+        );
+        // the pot keeps the 600 the pool did not hold.
+        assert_eq!(Balances::free_balance(AtochaModule::account_id()), 601);
+        assert_eq!(AtochaModule::puzzle_payouts(toVec("PUZZLE_HASH")), 500);
+    });
+}
+
+#[test]
+fn test_undelivered_hint_refund() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::add_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_hint_commitment(b"HINT"),
+            None,
+            Some(20),
+        ));
+        assert_ok!(AtochaModule::buy_hint(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            0,
+        ));
+        assert_ok!(AtochaModule::buy_hint(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+            0,
+        ));
+
+        // at most 2 accounts can buy one hint.
+        assert_eq!(
+            AtochaModule::puzzle_hints(toVec("PUZZLE_HASH"), 0)
+                .unwrap()
+                .purchase_count,
+            2
+        );
+        assert_noop!(
+            AtochaModule::buy_hint(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                0,
+            ),
+            Error::<Test>::TooManyHintPurchases
+        );
+        assert_ok!(AtochaModule::deliver_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            0,
+            CONST_ORIGIN_IS_ANSWER_3,
+            toVec("SEALED_HINT"),
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        assert_eq!(AtochaModule::puzzle_prize_pool(toVec("PUZZLE_HASH")), 640);
+
+        // the hint which was not delivered is refunded before the pool is split.
+        assert_ok!(AtochaModule::force_reveal_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+        ));
+        assert_has_event(
+            AtochaEvent::TicketRefunded(CONST_ORIGIN_IS_ANSWER_2, toVec("PUZZLE_HASH"), 20).into(),
+        );
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_ANSWER_2), 1_000_000);
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_3),
+            1_000_000 - 20
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 500 + 558
+        );
+    });
+}
+
+#[test]
+fn test_slot_puzzle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        // the creator signs `answer + nonce` of every slot.
+        let slots_signed: Vec<Vec<u8>> = ["SLOT_A", "SLOT_B", "SLOT_C"]
+            .iter()
+            .map(|answer| {
+                let mut message = toVec(answer);
+                message.extend_from_slice(b"NONCE");
+                TestSignature(CONST_ORIGIN_IS_CREATOR, message).encode()
+            })
+            .collect();
+        assert_ok!(AtochaModule::create_slot_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            slots_signed,
+            toVec("NONCE"),
+            100,
+            50,
+        ));
+        let slot_hashes = |answers: [&str; 3]| -> Vec<Vec<u8>> {
+            answers
+                .iter()
+                .map(|answer| AtochaModule::make_answer_hash(&toVec(answer)))
+                .collect()
+        };
+
+        // a slot puzzle takes an answer hash for every slot.
+        assert_noop!(
+            AtochaModule::answer_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_answer_hash(&toVec("SLOT_A")),
+                100,
+            ),
+            Error::<Test>::SlotAnswersRequired
+        );
+        assert_noop!(
+            AtochaModule::answer_puzzle_slots(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                slot_hashes(["SLOT_A", "SLOT_B", "SLOT_C"])[..2].to_vec(),
+                100,
+            ),
+            Error::<Test>::SlotCountMismatch
+        );
+        assert_noop!(
+            AtochaModule::answer_puzzle_slots(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                [
+                    slot_hashes(["SLOT_A", "SLOT_B", "SLOT_C"]),
+                    slot_hashes(["SLOT_D", "SLOT_E", "SLOT_F"])[..1].to_vec(),
+                ]
+                .concat(),
+                100,
+            ),
+            Error::<Test>::SlotCountMismatch
+        );
+        assert_ok!(AtochaModule::answer_puzzle_slots(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            slot_hashes(["SLOT_A", "SLOT_B", "SLOT_C"]),
+            100,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle_slots(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            slot_hashes(["SLOT_A", "WRONG", "SLOT_C"]),
+            100,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle_slots(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+            slot_hashes(["WRONG", "SLOT_C", "SLOT_B"]),
+            100,
+        ));
+
+        System::set_block_number(15);
+        assert_noop!(
+            AtochaModule::reveal_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("SLOT_A"),
+                toVec("NONCE"),
+            ),
+            Error::<Test>::SlotAnswersRequired
+        );
+        assert_noop!(
+            AtochaModule::reveal_puzzle_slots(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                vec![toVec("SLOT_A"), toVec("SLOT_C"), toVec("SLOT_B")],
+                toVec("NONCE"),
+            ),
+            Error::<Test>::InvalidAnswerSignature
+        );
+        assert_ok!(AtochaModule::reveal_puzzle_slots(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            vec![toVec("SLOT_A"), toVec("SLOT_B"), toVec("SLOT_C")],
+            toVec("NONCE"),
+        ));
+        assert_has_event(
+            AtochaEvent::AnswerSlotsSolved(
+                CONST_ORIGIN_IS_ANSWER_2,
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_answer_hash(
+                    &slot_hashes(["SLOT_A", "WRONG", "SLOT_C"]).encode(),
+                ),
+                2,
+            )
+            .into(),
+        );
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_SOLVED
+        );
+
+        // 10% creator fee, the rest is split 3:2 by the slots each answer got right.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_CREATOR),
+            1_000_000 - 100 + 40
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 100 + 216
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_2),
+            1_000_000 - 100 + 144
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_3),
+            1_000_000 - 100
+        );
+
+        // the points are scaled the same way.
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_ANSWER_1).points,
+            100
+        );
+        assert_eq!(
+            AtochaModule::player_scores(CONST_ORIGIN_IS_ANSWER_2).points,
+            66
+        );
+    });
+}
+
+#[test]
+fn test_storage_deposits() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        let puzzle_deposit = AtochaModule::puzzle_deposits(toVec("PUZZLE_HASH"));
+        assert_eq!(
+            Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR),
+            puzzle_deposit
+        );
+        assert_ok!(AtochaModule::add_hint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_hint_commitment(b"HINT"),
+            Some(10),
+            None,
+        ));
+        // 1 per byte of the hint commitment.
+        assert_eq!(
+            AtochaModule::puzzle_deposits(toVec("PUZZLE_HASH")),
+            puzzle_deposit + 32
+        );
+        assert_eq!(
+            Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR),
+            puzzle_deposit + 32
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        assert_eq!(Balances::reserved_balance(CONST_ORIGIN_IS_ANSWER_1), 5);
+
+        // the deposits are returned once the puzzle is solved.
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        assert_eq!(Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR), 0);
+        assert_eq!(Balances::reserved_balance(CONST_ORIGIN_IS_ANSWER_1), 0);
+        assert_eq!(AtochaModule::puzzle_deposits(toVec("PUZZLE_HASH")), 0);
+
+        // and when a puzzle is revoked.
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert!(Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR) > 0);
+        assert_ok!(AtochaModule::revoke_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH_2"),
+        ));
+        assert_eq!(Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR), 0);
+    });
+}
+
+#[test]
+fn test_prune_and_reap_puzzles() {
+    new_test_ext().execute_with(|| {
+        REAP_BOUNTY.with(|bounty| *bounty.borrow_mut() = 5);
+        System::set_block_number(5);
+
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("WRONG_TEXT")),
+            400,
+        ));
+        handle_create_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH_2",
+            "ANSWER_SIGNED",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_noop!(
+            AtochaModule::reap_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::PuzzleNotClosed
+        );
+
+        // the reap bounty stays reserved once the puzzles are closed.
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        System::set_block_number(20);
+        assert_ok!(AtochaModule::force_close_puzzle(
+            Origin::root(),
+            toVec("PUZZLE_HASH_2"),
+        ));
+        assert_eq!(
+            AtochaModule::puzzle_closed_at(toVec("PUZZLE_HASH")),
+            Some(15)
+        );
+        assert_eq!(AtochaModule::prunable_at(120), vec![toVec("PUZZLE_HASH_2")]);
+        assert_eq!(Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR), 5 + 5);
+
+        // anyone can reap a puzzle after the retention period.
+        System::set_block_number(114);
+        assert_noop!(
+            AtochaModule::reap_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+                toVec("PUZZLE_HASH"),
+            ),
+            Error::<Test>::RetentionPeriodNotOver
+        );
+        let answer_root =
+            AtochaModule::answer_root(&AtochaModule::answers_in_order(&toVec("PUZZLE_HASH")));
+        System::set_block_number(115);
+        assert_ok!(AtochaModule::reap_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+        ));
+        assert_has_event(
+            AtochaEvent::PuzzleReaped(CONST_ORIGIN_IS_ANSWER_3, toVec("PUZZLE_HASH"), 5).into(),
+        );
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_3),
+            1_000_000 + 5
+        );
+        assert_eq!(AtochaModule::puzzle_info(toVec("PUZZLE_HASH")), None);
+        assert_eq!(AtochaModule::puzzle_answer_count(toVec("PUZZLE_HASH")), 0);
+        assert!(AtochaModule::answers_by_account(
+            CONST_ORIGIN_IS_ANSWER_1,
+            (
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT"))
+            )
+        )
+        .is_none());
+        assert_eq!(
+            AtochaModule::puzzle_archive(toVec("PUZZLE_HASH")),
+            Some(PuzzleArchiveData {
+                account: CONST_ORIGIN_IS_CREATOR,
+                puzzle_status: PUZZLE_STATUS_IS_SOLVED,
+                create_bn: 5,
+                closed_bn: 15,
+                winners: vec![CONST_ORIGIN_IS_ANSWER_1],
+                answer_count: 2,
+                answer_root,
+                payouts: 1000,
+            })
+        );
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_SIGNED"),
+                toVec("NONCE"),
+                100,
+                50,
+                None,
+            ),
+            Error::<Test>::PuzzleAlreadyExist
+        );
+
+        // the other puzzle is pruned in `on_idle`, the bounty goes back to the creator.
+        AtochaModule::on_idle(120, 1_000_000_000);
+        assert_has_event(AtochaEvent::PuzzleArchived(toVec("PUZZLE_HASH_2"), 20).into());
+        assert_eq!(AtochaModule::puzzle_info(toVec("PUZZLE_HASH_2")), None);
+        assert_eq!(Balances::reserved_balance(CONST_ORIGIN_IS_CREATOR), 0);
+        assert_eq!(AtochaModule::prune_cursor(), 121);
+    });
+}
+
+#[test]
+fn test_merkle_proof() {
+    for leaf_count in 1..=5u8 {
+        let leaves: Vec<merkle::MerkleHash> = (0..leaf_count)
+            .map(|leaf| merkle::hash_leaf(&[leaf]))
+            .collect();
+        let root = merkle::merkle_root(&leaves);
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = merkle::merkle_proof(&leaves, index);
+            assert!(merkle::verify_proof(
+                &root,
+                leaf,
+                index,
+                leaves.len(),
+                &proof
+            ));
+            assert!(!merkle::verify_proof(
+                &root,
+                &merkle::hash_leaf(b"OTHER"),
+                index,
+                leaves.len(),
+                &proof
+            ));
+        }
+    }
+    assert_eq!(merkle::merkle_root(&[]), merkle::EMPTY_ROOT);
+}
+
+#[test]
+fn test_answer_proofs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        for (answer_account, answer_text) in vec![
+            (CONST_ORIGIN_IS_ANSWER_1, "WRONG_TEXT"),
+            (CONST_ORIGIN_IS_ANSWER_2, "ANSWER_TEXT"),
+            (CONST_ORIGIN_IS_ANSWER_1, "OTHER_TEXT"),
+        ] {
+            assert_ok!(AtochaModule::answer_puzzle(
+                Origin::signed(answer_account),
+                toVec("PUZZLE_HASH"),
+                AtochaModule::make_answer_hash(&toVec(answer_text)),
+                500,
+            ));
+        }
+
+        // an open puzzle is proved against its current answers.
+        let answer_proofs =
+            AtochaModule::answer_proofs(&toVec("PUZZLE_HASH"), &CONST_ORIGIN_IS_ANSWER_1);
+        assert_eq!(answer_proofs.len(), 2);
+        assert_eq!(AtochaModule::puzzle_answer_root(toVec("PUZZLE_HASH")), None);
+
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        let answer_root = AtochaModule::puzzle_answer_root(toVec("PUZZLE_HASH")).unwrap();
+        assert_has_event(AtochaEvent::AnswerRootRecorded(toVec("PUZZLE_HASH"), answer_root).into());
+
+        // the root stays the same once the answer status changes.
+        let answer_proofs =
+            AtochaModule::answer_proofs(&toVec("PUZZLE_HASH"), &CONST_ORIGIN_IS_ANSWER_1);
+        assert_eq!(answer_proofs.len(), 2);
+        for answer_proof in answer_proofs {
+            assert_eq!(answer_proof.root.0, answer_root);
+            assert_eq!(answer_proof.leaf_count, 3);
+            let leaf = merkle::hash_leaf(
+                &(
+                    &answer_proof.account,
+                    &answer_proof.answer_hash,
+                    answer_proof.ticket,
+                    answer_proof.create_bn,
+                )
+                    .encode(),
+            );
+            let proof: Vec<merkle::MerkleHash> =
+                answer_proof.proof.iter().map(|node| node.0).collect();
+            assert!(merkle::verify_proof(
+                &answer_root,
+                &leaf,
+                answer_proof.leaf_index as usize,
+                answer_proof.leaf_count as usize,
+                &proof
+            ));
+        }
+        assert!(
+            AtochaModule::answer_proofs(&toVec("PUZZLE_HASH"), &CONST_ORIGIN_IS_ANSWER_3)
+                .is_empty()
+        );
+
+        // the recorded root moves into the archive when the puzzle is pruned.
+        System::set_block_number(115);
+        assert_ok!(AtochaModule::reap_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+        ));
+        assert_eq!(AtochaModule::puzzle_answer_root(toVec("PUZZLE_HASH")), None);
+        assert_eq!(
+            AtochaModule::puzzle_archive(toVec("PUZZLE_HASH"))
+                .unwrap()
+                .answer_root,
+            answer_root
+        );
+        assert!(
+            AtochaModule::answer_proofs(&toVec("PUZZLE_HASH"), &CONST_ORIGIN_IS_ANSWER_1)
+                .is_empty()
+        );
+    });
+}
+
+#[test]
+fn test_settlement_commitment() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        handle_create_signed_puzzle(
+            CONST_ORIGIN_IS_CREATOR,
+            "PUZZLE_HASH",
+            "ANSWER_TEXT",
+            "NONCE",
+            100,
+            50,
+        );
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            500,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("WRONG_TEXT")),
+            400,
+        ));
+
+        System::set_block_number(15);
+        assert_eq!(
+            AtochaModule::settlement_commitment(),
+            (0, merkle::EMPTY_ROOT)
+        );
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        let settlement_leaf = AtochaModule::settlement_leaf(
+            &toVec("PUZZLE_HASH"),
+            &AtochaModule::puzzle_answer_root(toVec("PUZZLE_HASH")).unwrap(),
+            &[CONST_ORIGIN_IS_ANSWER_1],
+        );
+        assert_eq!(
+            AtochaModule::settled_puzzles(15),
+            vec![(toVec("PUZZLE_HASH"), settlement_leaf)]
+        );
+
+        // the leaf of the next block commits to the settlement.
+        System::set_block_number(16);
+        AtochaModule::on_initialize(16);
+        assert_eq!(
+            AtochaModule::settlement_commitment(),
+            (1, merkle::merkle_root(&[settlement_leaf]))
+        );
+
+        System::set_block_number(17);
+        AtochaModule::on_initialize(17);
+        assert_eq!(
+            AtochaModule::settlement_commitment(),
+            (0, merkle::EMPTY_ROOT)
+        );
+
+        // the settlement can be proved for the retention period.
+        let settlement_proof = AtochaModule::settlement_proof(&toVec("PUZZLE_HASH")).unwrap();
+        assert_eq!(settlement_proof.settled_bn, 15);
+        assert_eq!(settlement_proof.leaf.0, settlement_leaf);
+        assert_eq!(
+            (settlement_proof.leaf_index, settlement_proof.leaf_count),
+            (0, 1)
+        );
+        assert_eq!(
+            settlement_proof.root.0,
+            merkle::merkle_root(&[settlement_leaf])
+        );
+
+        System::set_block_number(117);
+        AtochaModule::on_initialize(117);
+        assert!(AtochaModule::settled_puzzles(15).is_empty());
+        assert_eq!(AtochaModule::settlement_proof(&toVec("PUZZLE_HASH")), None);
+    });
+}
+
+#[test]
+fn test_asset_puzzle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        assert_ok!(Assets::force_create(
+            Origin::root(),
+            0,
+            CONST_ORIGIN_IS_CREATOR,
+            true,
+            1
+        ));
+        for account in vec![
+            CONST_ORIGIN_IS_CREATOR,
+            CONST_ORIGIN_IS_ANSWER_1,
+            CONST_ORIGIN_IS_ANSWER_2,
+        ] {
+            assert_ok!(Assets::mint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                0,
+                account,
+                1000
+            ));
+        }
+
+        let answer_signed =
+            TestSignature(CONST_ORIGIN_IS_CREATOR, toVec("ANSWER_TEXTNONCE")).encode();
+        assert_ok!(AtochaModule::create_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            answer_signed,
+            toVec("NONCE"),
+            100,
+            50,
+            Some(0),
+        ));
+        assert_eq!(AtochaModule::puzzle_asset(toVec("PUZZLE_HASH")), Some(0));
+
+        // the answers pay their tickets in the asset of the puzzle.
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            200,
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_2),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("WRONG_TEXT")),
+            300,
+        ));
+        assert!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_3),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("OTHER_TEXT")),
+            300,
+        )
+        .is_err());
+        assert_eq!(AtochaModule::puzzle_answer_count(toVec("PUZZLE_HASH")), 2);
+        assert_eq!(Assets::balance(0, CONST_ORIGIN_IS_ANSWER_1), 1000 - 200);
+        // the creator of the first puzzle in the asset pays its `min_balance` into the pot.
+        assert_eq!(
+            Assets::balance(0, AtochaModule::account_id()),
+            1 + 100 + 200 + 300
+        );
+        // only the storage deposit is taken in the native currency.
+        assert_eq!(
+            Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1),
+            1_000_000 - 5
+        );
+
+        // the creator fee and the reward are paid in the asset as well.
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        assert_eq!(
+            Assets::balance(0, CONST_ORIGIN_IS_CREATOR),
+            1000 - 1 - 100 + 60
+        );
+        assert_eq!(
+            Assets::balance(0, CONST_ORIGIN_IS_ANSWER_1),
+            1000 - 200 + 540
+        );
+        assert_eq!(Assets::balance(0, CONST_ORIGIN_IS_ANSWER_2), 1000 - 300);
+        assert_eq!(Assets::balance(0, AtochaModule::account_id()), 1);
+        assert_eq!(Balances::free_balance(CONST_ORIGIN_IS_ANSWER_1), 1_000_000);
+    });
+}
+
+#[test]
+fn test_asset_puzzle_not_sufficient() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        // a bridged asset which does not provide for the accounts holding it.
+        assert_ok!(Assets::force_create(
+            Origin::root(),
+            0,
+            CONST_ORIGIN_IS_CREATOR,
+            false,
+            10
+        ));
+        for account in vec![CONST_ORIGIN_IS_CREATOR, CONST_ORIGIN_IS_ANSWER_1] {
+            assert_ok!(Assets::mint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                0,
+                account,
+                1000
+            ));
+        }
+
+        // the existential deposit of the pot provides for its asset account.
+        let answer_signed =
+            TestSignature(CONST_ORIGIN_IS_CREATOR, toVec("ANSWER_TEXTNONCE")).encode();
+        assert_ok!(AtochaModule::create_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            answer_signed,
+            toVec("NONCE"),
+            100,
+            50,
+            Some(0),
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            200,
+        ));
+        assert_eq!(
+            Assets::balance(0, AtochaModule::account_id()),
+            10 + 100 + 200
+        );
+
+        // the whole pool is paid out, the pot keeps the `min_balance`.
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        assert_eq!(
+            Assets::balance(0, CONST_ORIGIN_IS_CREATOR),
+            1000 - 10 - 100 + 30
+        );
+        assert_eq!(
+            Assets::balance(0, CONST_ORIGIN_IS_ANSWER_1),
+            1000 - 200 + 270
+        );
+        assert_eq!(Assets::balance(0, AtochaModule::account_id()), 10);
+    });
+}
+
+#[test]
+fn test_unpaid_payout_is_claimable() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        assert_ok!(Assets::force_create(
+            Origin::root(),
+            0,
+            CONST_ORIGIN_IS_CREATOR,
+            true,
+            50
+        ));
+        for account in vec![CONST_ORIGIN_IS_CREATOR, CONST_ORIGIN_IS_ANSWER_1] {
+            assert_ok!(Assets::mint(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                0,
+                account,
+                1000
+            ));
+        }
+        let answer_signed =
+            TestSignature(CONST_ORIGIN_IS_CREATOR, toVec("ANSWER_TEXTNONCE")).encode();
+        assert_ok!(AtochaModule::create_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            answer_signed,
+            toVec("NONCE"),
+            11,
+            50,
+            Some(0),
+        ));
+        assert_ok!(AtochaModule::answer_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            AtochaModule::make_answer_hash(&toVec("ANSWER_TEXT")),
+            20,
+        ));
+        // the winner empties its asset account, a reward below `min_balance` can not be paid
+        // to it.
+        assert_ok!(Assets::transfer(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            0,
+            CONST_ORIGIN_IS_ANSWER_2,
+            980
+        ));
+
+        // the settlement still goes through and pays the creator fee.
+        System::set_block_number(15);
+        assert_ok!(AtochaModule::reveal_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            toVec("PUZZLE_HASH"),
+            toVec("ANSWER_TEXT"),
+            toVec("NONCE"),
+        ));
+        assert_eq!(
+            AtochaModule::puzzle_info(toVec("PUZZLE_HASH"))
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_SOLVED
+        );
+        assert_eq!(
+            Assets::balance(0, CONST_ORIGIN_IS_CREATOR),
+            1000 - 50 - 11 + 3
+        );
+        assert_eq!(
+            AtochaModule::unpaid_payouts(CONST_ORIGIN_IS_ANSWER_1, (toVec("PUZZLE_HASH"), Some(0))),
+            Some(28)
+        );
+        assert_has_event(
+            AtochaEvent::PayoutDeferred(CONST_ORIGIN_IS_ANSWER_1, toVec("PUZZLE_HASH"), 28).into(),
+        );
+        assert_eq!(AtochaModule::puzzle_payouts(toVec("PUZZLE_HASH")), 3);
+
+        // the payout is claimable in its asset once the account can hold it.
+        assert!(AtochaModule::claim_payout(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            Some(0),
+        )
+        .is_err());
+        assert_noop!(
+            AtochaModule::claim_payout(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                None,
+            ),
+            Error::<Test>::NoUnpaidPayout
+        );
+        assert_ok!(Assets::mint(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            0,
+            CONST_ORIGIN_IS_ANSWER_1,
+            100
+        ));
+        assert_ok!(AtochaModule::claim_payout(
+            Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+            toVec("PUZZLE_HASH"),
+            Some(0),
+        ));
+        assert_eq!(Assets::balance(0, CONST_ORIGIN_IS_ANSWER_1), 100 + 28);
+        assert_eq!(AtochaModule::puzzle_payouts(toVec("PUZZLE_HASH")), 3 + 28);
+        assert_eq!(Assets::balance(0, AtochaModule::account_id()), 50);
+        assert_noop!(
+            AtochaModule::claim_payout(
+                Origin::signed(CONST_ORIGIN_IS_ANSWER_1),
+                toVec("PUZZLE_HASH"),
+                Some(0),
+            ),
+            Error::<Test>::NoUnpaidPayout
+        );
+    });
+}
+
+#[test]
+fn test_puzzle_deadline_schedule() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+
+        // a puzzle must accept answers for at least one block.
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                toVec("PUZZLE_HASH"),
+                toVec("ANSWER_SIGNED"),
+                toVec("NONCE"),
+                10,
+                0,
+                None
+            ),
+            Error::<Test>::InvalidDuration
+        );
+
+        // the puzzles fill the blocks from their deadline on, 2 in each block.
+        let scheduled_count = 2 * MAX_SCHEDULE_DELAY;
+        for puzzle_index in 0..scheduled_count {
+            assert_ok!(AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                puzzle_index.encode(),
+                toVec("ANSWER_SIGNED"),
+                toVec("NONCE"),
+                10,
+                50,
+                None
+            ));
+        }
+        for expiry_bn in 55..55 + MAX_SCHEDULE_DELAY {
+            assert_eq!(AtochaModule::puzzle_deadlines(expiry_bn).len(), 2);
+        }
+        assert_noop!(
+            AtochaModule::create_puzzle(
+                Origin::signed(CONST_ORIGIN_IS_CREATOR),
+                scheduled_count.encode(),
+                toVec("ANSWER_SIGNED"),
+                toVec("NONCE"),
+                10,
+                50,
+                None
+            ),
+            Error::<Test>::ScheduleFull
+        );
+
+        // a revoked puzzle is removed from the block it waits in.
+        let last_hash = (scheduled_count - 1).encode();
+        assert_ok!(AtochaModule::revoke_puzzle(
+            Origin::signed(CONST_ORIGIN_IS_CREATOR),
+            last_hash.clone()
+        ));
+        assert!(!AtochaModule::puzzle_deadlines(55 + MAX_SCHEDULE_DELAY - 1).contains(&last_hash));
+
+        // a puzzle which waits for a later block expires there.
+        let delayed_hash = 2u64.encode();
+        System::set_block_number(55);
+        AtochaModule::on_initialize(55);
+        assert_eq!(
+            AtochaModule::puzzle_info(&delayed_hash)
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_SOLVING
+        );
+        System::set_block_number(56);
+        AtochaModule::on_initialize(56);
+        assert_eq!(
+            AtochaModule::puzzle_info(&delayed_hash)
+                .unwrap()
+                .puzzle_status,
+            PUZZLE_STATUS_IS_UP_TO_TIME
+        );
+        assert_has_event(AtochaEvent::PuzzleExpired(delayed_hash, 56).into());
+    });
+}
+
 fn handle_create_puzzle(
     account_id: u64,
     puzzle_hash: &str,
     answer_signed: &str,
     answer_nonce: &str,
-    ticket: PuzzleTicket,
+    ticket: BalanceOf<Test>,
     duration: DurationBn,
 ) {
     let origin = Origin::signed(account_id);
     let puzzle_hash = puzzle_hash.as_bytes().to_vec();
     let answer_signed = answer_signed.as_bytes().to_vec();
     let answer_nonce = answer_nonce.as_bytes().to_vec();
-    let puzzle_version: PuzzleVersion = 1;
 
     // Dispatch a signed extrinsic.
     assert_ok!(AtochaModule::create_puzzle(
@@ -199,7 +3386,7 @@ fn handle_create_puzzle(
         answer_nonce.clone(),
         ticket.clone(),
         duration.clone(),
-        puzzle_version.clone()
+        None
     ));
 }
 
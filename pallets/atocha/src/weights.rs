@@ -0,0 +1,406 @@
+//! Weights for pallet_atocha
+//!
+//! NOT GENERATED YET: the storage reads and writes follow the benchmarks in `benchmarking.rs`,
+//! the times are estimates. Generate this file with the command below on the reference
+//! hardware, and wire the generated `SubstrateWeight` into the runtime in place of
+//! `EstimatedWeight`, before the runtime goes to a live chain.
+
+// Command to regenerate:
+// ./target/release/appchain-atocha
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_atocha
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./pallets/atocha/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_atocha.
+pub trait WeightInfo {
+    fn create_puzzle(h: u32) -> Weight;
+    fn answer_puzzle(h: u32, a: u32) -> Weight;
+    fn create_slot_puzzle(s: u32) -> Weight;
+    fn revise_puzzle(h: u32) -> Weight;
+    fn answer_puzzle_slots(s: u32, a: u32) -> Weight;
+    fn commit_answer(h: u32, a: u32) -> Weight;
+    fn check_answer_signed() -> Weight;
+    fn reveal_answers(a: u32, p: u32) -> Weight;
+    fn reveal_slot_answers(s: u32, a: u32, p: u32) -> Weight;
+    fn reveal_answer() -> Weight;
+    fn settle_puzzle(a: u32, p: u32) -> Weight;
+    fn refund_puzzle(a: u32, p: u32) -> Weight;
+    fn revoke_puzzle(a: u32, p: u32) -> Weight;
+    fn add_hint() -> Weight;
+    fn buy_hint() -> Weight;
+    fn reveal_hint(h: u32) -> Weight;
+    fn deliver_hint(h: u32) -> Weight;
+    fn reap_puzzle(a: u32, p: u32) -> Weight;
+    fn claim_payout() -> Weight;
+    fn schedule_season() -> Weight;
+    fn force_close_puzzle(a: u32, p: u32) -> Weight;
+    fn force_reveal_puzzle(a: u32, p: u32) -> Weight;
+    fn set_blacklisted() -> Weight;
+    fn set_paused() -> Weight;
+}
+
+/// Estimated weights for pallet_atocha, not measured on any hardware. Use the generated
+/// `SubstrateWeight` instead once this file is generated.
+pub struct EstimatedWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for EstimatedWeight<T> {
+    fn create_puzzle(h: u32) -> Weight {
+        (62_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(17 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn answer_puzzle(h: u32, _a: u32) -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add((6_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn create_slot_puzzle(s: u32) -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(17 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn revise_puzzle(h: u32) -> Weight {
+        (83_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(20 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+    fn answer_puzzle_slots(s: u32, _a: u32) -> Weight {
+        (76_000_000 as Weight)
+            .saturating_add((1_100_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+    fn commit_answer(h: u32, _a: u32) -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add((6_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+    fn check_answer_signed() -> Weight {
+        (48_000_000 as Weight)
+    }
+    fn reveal_answers(a: u32, p: u32) -> Weight {
+        (97_000_000 as Weight)
+            .saturating_add((38_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn reveal_slot_answers(s: u32, a: u32, p: u32) -> Weight {
+        (104_000_000 as Weight)
+            .saturating_add((2_300_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add((52_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn reveal_answer() -> Weight {
+        (58_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn settle_puzzle(a: u32, p: u32) -> Weight {
+        (92_000_000 as Weight)
+            .saturating_add((54_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn refund_puzzle(a: u32, p: u32) -> Weight {
+        (48_000_000 as Weight)
+            .saturating_add((41_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((19_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn revoke_puzzle(a: u32, p: u32) -> Weight {
+        (56_000_000 as Weight)
+            .saturating_add((45_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((22_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(16 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+    }
+    fn add_hint() -> Weight {
+        (47_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn buy_hint() -> Weight {
+        (61_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn reveal_hint(h: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn deliver_hint(h: u32) -> Weight {
+        (49_000_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn reap_puzzle(a: u32, p: u32) -> Weight {
+        (88_000_000 as Weight)
+            .saturating_add((9_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes(25 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn claim_payout() -> Weight {
+        (57_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn schedule_season() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn force_close_puzzle(a: u32, p: u32) -> Weight {
+        (79_000_000 as Weight)
+            .saturating_add((44_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((19_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn force_reveal_puzzle(a: u32, p: u32) -> Weight {
+        (99_000_000 as Weight)
+            .saturating_add((38_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn set_blacklisted() -> Weight {
+        (16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_paused() -> Weight {
+        (14_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_puzzle(h: u32) -> Weight {
+        (62_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn answer_puzzle(h: u32, _a: u32) -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add((6_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn create_slot_puzzle(s: u32) -> Weight {
+        (68_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+    fn revise_puzzle(h: u32) -> Weight {
+        (83_000_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(RocksDbWeight::get().reads(20 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(12 as Weight))
+    }
+    fn answer_puzzle_slots(s: u32, _a: u32) -> Weight {
+        (76_000_000 as Weight)
+            .saturating_add((1_100_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+    fn commit_answer(h: u32, _a: u32) -> Weight {
+        (71_000_000 as Weight)
+            .saturating_add((6_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+    fn check_answer_signed() -> Weight {
+        (48_000_000 as Weight)
+    }
+    fn reveal_answers(a: u32, p: u32) -> Weight {
+        (97_000_000 as Weight)
+            .saturating_add((38_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn reveal_slot_answers(s: u32, a: u32, p: u32) -> Weight {
+        (104_000_000 as Weight)
+            .saturating_add((2_300_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add((52_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn reveal_answer() -> Weight {
+        (58_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn settle_puzzle(a: u32, p: u32) -> Weight {
+        (92_000_000 as Weight)
+            .saturating_add((54_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn refund_puzzle(a: u32, p: u32) -> Weight {
+        (48_000_000 as Weight)
+            .saturating_add((41_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((19_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn revoke_puzzle(a: u32, p: u32) -> Weight {
+        (56_000_000 as Weight)
+            .saturating_add((45_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((22_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(16 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+    }
+    fn add_hint() -> Weight {
+        (47_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn buy_hint() -> Weight {
+        (61_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn reveal_hint(h: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn deliver_hint(h: u32) -> Weight {
+        (49_000_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn reap_puzzle(a: u32, p: u32) -> Weight {
+        (88_000_000 as Weight)
+            .saturating_add((9_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(25 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn claim_payout() -> Weight {
+        (57_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn schedule_season() -> Weight {
+        (21_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn force_close_puzzle(a: u32, p: u32) -> Weight {
+        (79_000_000 as Weight)
+            .saturating_add((44_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((19_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn force_reveal_puzzle(a: u32, p: u32) -> Weight {
+        (99_000_000 as Weight)
+            .saturating_add((38_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((21_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+    }
+    fn set_blacklisted() -> Weight {
+        (16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_paused() -> Weight {
+        (14_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
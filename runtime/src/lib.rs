@@ -41,6 +41,7 @@ use pallet_transaction_payment::CurrencyAdapter;
 use frame_support::{
 	weights::DispatchClass,
 	traits::U128CurrencyToVote,
+	PalletId,
 };
 use frame_system::{
 	EnsureRoot,
@@ -131,10 +132,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 101,
+	spec_version: 104,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 3,
 };
 
 /// Since BABE is probabilistic this is the average expected block time that
@@ -508,9 +509,85 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const AtochaPalletId: PalletId = PalletId(*b"py/atoch");
+	pub const AtochaSeasonTreasuryId: PalletId = PalletId(*b"py/atsea");
+	pub const AtochaCreatorFee: Perbill = Perbill::from_percent(5);
+	pub const AtochaPrizeDistribution: pallet_atocha::PrizeDistribution =
+		pallet_atocha::PrizeDistribution::EqualSplit;
+	pub const AtochaMaxExpiriesPerBlock: u32 = 50;
+	pub const AtochaRevealGracePeriod: BlockNumber = 1 * DAYS;
+	pub const AtochaUnrevealedSlash: Perbill = Perbill::from_percent(50);
+	pub const AtochaRevokeCutoff: BlockNumber = 1 * HOURS;
+	pub const AtochaAnswerRevealWindow: BlockNumber = 1 * DAYS;
+	pub const AtochaMaxHashLength: u32 = 128;
+	pub const AtochaMaxAnswerLength: u32 = 256;
+	pub const AtochaMaxSignatureLength: u32 = 128;
+	pub const AtochaMaxAnswersPerPuzzle: u32 = 1000;
+	pub const AtochaPuzzleDepositBase: Balance = 1 * DOLLARS;
+	pub const AtochaDepositPerByte: Balance = 1 * CENTS;
+	pub const AtochaAnswerDeposit: Balance = 10 * CENTS;
+	pub const AtochaRetentionPeriod: BlockNumber = 30 * DAYS;
+	pub const AtochaReapBounty: Balance = 10 * CENTS;
+	pub const AtochaMaxAnswerSlots: u32 = 16;
+	pub const AtochaMaxHintsPerPuzzle: u32 = 5;
+	pub const AtochaMaxHintPurchases: u32 = 100;
+	pub const AtochaMaxHintLength: u32 = 1024;
+	pub const AtochaLeaderboardSize: u32 = 100;
+}
+
+/// 200 points for a solve in the creation block, falling towards 100 the longer it takes,
+/// 150 after a day. The creator gets 10 points per winner, at most 100.
+pub struct AtochaPointsFormula;
+impl pallet_atocha::PointsFormula for AtochaPointsFormula {
+	fn solver_points(solve_blocks: u64) -> u64 {
+		let day = DAYS as u64;
+		100 + 100 * day / day.saturating_add(solve_blocks)
+	}
+
+	fn creator_points(winner_count: u32) -> u64 {
+		(10 * winner_count as u64).min(100)
+	}
+}
+
 /// 定义我们的配置模块接口
 impl pallet_atocha::Config for Runtime {
 	type Event = Event;
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+	type Currency = Balances;
+	// the tokens bridged in by `OctopusAppchain`, e.g. asset 0 for `test-stable.testnet`.
+	type AssetId = u32;
+	type Assets = Assets;
+	type PalletId = AtochaPalletId;
+	type CreatorFee = AtochaCreatorFee;
+	type PrizeDistribution = AtochaPrizeDistribution;
+	type MaxExpiriesPerBlock = AtochaMaxExpiriesPerBlock;
+	type RevealGracePeriod = AtochaRevealGracePeriod;
+	type UnrevealedSlash = AtochaUnrevealedSlash;
+	type RevokeCutoff = AtochaRevokeCutoff;
+	type AnswerRevealWindow = AtochaAnswerRevealWindow;
+	type MaxHashLength = AtochaMaxHashLength;
+	type MaxAnswerLength = AtochaMaxAnswerLength;
+	type MaxSignatureLength = AtochaMaxSignatureLength;
+	type MaxAnswersPerPuzzle = AtochaMaxAnswersPerPuzzle;
+	type PuzzleDepositBase = AtochaPuzzleDepositBase;
+	type DepositPerByte = AtochaDepositPerByte;
+	type AnswerDeposit = AtochaAnswerDeposit;
+	type RetentionPeriod = AtochaRetentionPeriod;
+	type ReapBounty = AtochaReapBounty;
+	type MaxAnswerSlots = AtochaMaxAnswerSlots;
+	type MaxHintsPerPuzzle = AtochaMaxHintsPerPuzzle;
+	type MaxHintPurchases = AtochaMaxHintPurchases;
+	type MaxHintLength = AtochaMaxHintLength;
+	type PointsFormula = AtochaPointsFormula;
+	type LeaderboardSize = AtochaLeaderboardSize;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type SeasonOrigin = EnsureRoot<AccountId>;
+	type SeasonTreasuryId = AtochaSeasonTreasuryId;
+	// estimates until `pallet_atocha::weights` is generated, this runtime must not go to a
+	// live chain before the generated `SubstrateWeight` replaces them.
+	type WeightInfo = pallet_atocha::weights::EstimatedWeight<Runtime>;
 }
 
 parameter_types! {
@@ -601,11 +678,41 @@ impl pallet_mmr::primitives::OnNewRoot<MmrHash> for DepositLog {
 	}
 }
 
+/// The layout version of `MmrLeaf`, bumped whenever a field is added or changed.
+pub const MMR_LEAF_VERSION: u8 = 1;
+
+/// The `pallet_mmr` leaf of a block: the parent block, and the number and the merkle root of
+/// the `pallet_atocha` settlement leaves of the puzzles settled in the parent block. The leaves
+/// added before the runtime upgrade that first ships `MMR_LEAF_VERSION` are the encoded
+/// `(parent_number, parent_hash)` only.
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, sp_runtime::RuntimeDebug)]
+pub struct MmrLeaf {
+	pub version: u8,
+	pub parent_number_and_hash: (BlockNumber, Hash),
+	pub settled_count: u32,
+	pub settlement_root: [u8; 32],
+}
+
+pub struct MmrLeafProvider;
+impl pallet_mmr::primitives::LeafDataProvider for MmrLeafProvider {
+	type LeafData = MmrLeaf;
+
+	fn leaf_data() -> Self::LeafData {
+		let (settled_count, settlement_root) = AtochaModule::settlement_commitment();
+		MmrLeaf {
+			version: MMR_LEAF_VERSION,
+			parent_number_and_hash: <frame_system::Pallet<Runtime> as pallet_mmr::primitives::LeafDataProvider>::leaf_data(),
+			settled_count,
+			settlement_root,
+		}
+	}
+}
+
 impl pallet_mmr::Config for Runtime {
 	const INDEXING_PREFIX: &'static [u8] = b"mmr";
 	type Hashing = Keccak256;
 	type Hash = MmrHash;
-	type LeafData = frame_system::Pallet<Self>;
+	type LeafData = MmrLeafProvider;
 	type OnNewRoot = DepositLog;
 	type WeightInfo = ();
 }
@@ -661,7 +768,7 @@ construct_runtime!(
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
 		// 引入 Atocha 的配置
-        AtochaModule: pallet_atocha::{Pallet, Call, Storage, Event<T>},
+        AtochaModule: pallet_atocha::{Pallet, Call, Storage, Config, Event<T>},
 	}
 );
 
@@ -889,6 +996,47 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_atocha_rpc_runtime_api::AtochaApi<Block, AccountId, Balance> for Runtime {
+		fn get_puzzle(puzzle_hash: Vec<u8>) -> Option<pallet_atocha_rpc_runtime_api::PuzzleDetails<AccountId, Balance>> {
+			AtochaModule::puzzle_details(&puzzle_hash)
+		}
+
+		fn list_answers(
+			puzzle_hash: Vec<u8>,
+			offset: u32,
+			limit: u32,
+		) -> Vec<pallet_atocha_rpc_runtime_api::AnswerDetails<AccountId, Balance>> {
+			AtochaModule::answer_details_page(&puzzle_hash, offset, limit)
+		}
+
+		fn puzzles_by_creator(
+			creator: AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<pallet_atocha_rpc_runtime_api::PuzzleDetails<AccountId, Balance>> {
+			AtochaModule::puzzle_details_by_creator(&creator, offset, limit)
+		}
+
+		fn puzzles_by_status(
+			puzzle_status: u8,
+			offset: u32,
+			limit: u32,
+		) -> Vec<pallet_atocha_rpc_runtime_api::PuzzleDetails<AccountId, Balance>> {
+			AtochaModule::puzzle_details_by_status(puzzle_status, offset, limit)
+		}
+
+		fn answer_proofs(
+			puzzle_hash: Vec<u8>,
+			account: AccountId,
+		) -> Vec<pallet_atocha_rpc_runtime_api::AnswerProof<AccountId, Balance>> {
+			AtochaModule::answer_proofs(&puzzle_hash, &account)
+		}
+
+		fn settlement_proof(puzzle_hash: Vec<u8>) -> Option<pallet_atocha_rpc_runtime_api::SettlementProof> {
+			AtochaModule::settlement_proof(&puzzle_hash)
+		}
+	}
+
 	impl pallet_mmr::primitives::MmrApi<
 		Block,
 		mmr::Hash,
@@ -956,6 +1104,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_atocha, AtochaModule);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)